@@ -0,0 +1,326 @@
+//
+// MIT License
+// 
+// Copyright (c) 2023 Wilhelm Ågren
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// 
+// File created: 2026-10-17
+// Last updated: 2026-10-17
+//
+
+use crate::datatype::DataType;
+use crate::error::unwrap;
+use crate::error::Result;
+use crate::error::RuneError;
+use crate::tensor::Tensor;
+
+use ndarray::ArrayD;
+
+use std::collections::HashSet;
+
+///
+/// The backward rule of an op. Given the gradient of the op output it 
+/// returns the gradient with respect to each of its parents, in the same
+/// order as the parents were registered. Any context that the rule needs,
+/// e.g. the operands of a multiplication, is captured by the closure.
+///
+pub type GradFn<T> = Box<dyn Fn(&ArrayD<T>) -> Vec<ArrayD<T>>>;
+
+///
+/// Order all tensors reachable from `root` such that every tensor comes 
+/// after all of its parents. Tensors are identified by their graph node, so
+/// a tensor used several times in the graph only shows up once. The 
+/// traversal is iterative to not overflow the stack on deep graphs.
+///
+fn topological_sort<T: DataType>(root: &Tensor<T>) -> Vec<Tensor<T>>
+{
+    let mut order = Vec::new();
+    let mut visited = HashSet::new();
+    let mut stack = vec![(root.clone(), false)];
+
+    while let Some((node, expanded)) = stack.pop()
+    {
+        if expanded
+        {
+            order.push(node);
+            continue;
+        }
+
+        if !visited.insert(node.id())
+        {
+            continue;
+        }
+
+        stack.push((node.clone(), true));
+        for parent in node.parents().iter()
+        {
+            if parent.requires_grad() && !visited.contains(&parent.id())
+            {
+                stack.push((parent.clone(), false));
+            }
+        }
+    }
+
+    order
+}
+
+impl<T: DataType> Tensor<T>
+{
+    ///
+    /// Compute the gradient of this scalar tensor with respect to every 
+    /// tensor in its graph that requires grad. Gradients are accumulated 
+    /// into the leaf tensors, so call `zero_grad` in between steps. The 
+    /// gradients of intermediate tensors are recomputed on every call.
+    ///
+    /// # Example
+    ///
+    /// let mut a = Tensor::<f32>::ones(&[1]);
+    /// a.set_requires_grad(true);
+    /// let b = a.mul(&a);
+    /// b.backward();
+    ///
+    /// >>> a.grad() = [2.0]
+    ///
+    pub fn backward(&self)
+    {
+        unwrap(self.try_backward())
+    }
+
+    ///
+    /// Same as `backward`, but returns an error instead of panicking when 
+    /// the output is not a scalar.
+    ///
+    pub fn try_backward(&self) -> Result<()>
+    {
+        if self.data().len() != 1
+        {
+            let reason = "backward requires a scalar output".to_string();
+            return Err(RuneError::invalid("backward", self.shape().dims(), reason));
+        }
+
+        if !self.requires_grad()
+        {
+            return Ok(());
+        }
+
+        let order = topological_sort(self);
+        for node in order.iter().filter(|n| !n.is_leaf())
+        {
+            node.zero_grad();
+        }
+
+        self.accumulate_grad(&ArrayD::<T>::ones(self.data().raw_dim()));
+        for node in order.iter().rev()
+        {
+            let grad_fn = match node.grad_fn()
+            {
+                Some(grad_fn) => grad_fn,
+                None => continue,
+            };
+
+            let grads = grad_fn(&node.grad());
+            for (parent, grad) in node.parents().iter().zip(grads.iter())
+            {
+                if parent.requires_grad()
+                {
+                    parent.accumulate_grad(grad);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+///
+/// Compare the gradients computed by `backward` against central finite 
+/// differences. `f` maps the inputs to an output tensor, which is summed 
+/// into a scalar before differentiating. Every input must require grad.
+/// Returns true if, for every input value, the analytic gradient `a` and 
+/// the numerical gradient `n` satisfy |a - n| <= tol * (1 + |n|).
+///
+/// # Example
+///
+/// let mut x = Tensor::<f64>::uniform(&[3, 4], -1.0, 1.0);
+/// x.set_requires_grad(true);
+///
+/// >>> gradcheck(|t| t[0].tanh(), &[x], 1e-6, 1e-5) = true
+///
+pub fn gradcheck<F>(f: F, inputs: &[Tensor<f64>], eps: f64, tol: f64) -> bool
+where F: Fn(&[Tensor<f64>]) -> Tensor<f64>
+{
+    assert!(
+        inputs.iter().all(|t| t.requires_grad()),
+        "Every input to gradcheck must require grad",
+    );
+
+    for input in inputs.iter()
+    {
+        input.zero_grad();
+    }
+    f(inputs).sum_all().backward();
+
+    for (i, input) in inputs.iter().enumerate()
+    {
+        let analytic = input.grad().as_standard_layout().into_owned();
+        let base = input.data().as_standard_layout().into_owned();
+
+        let eval = |idx: usize, delta: f64|
+        {
+            let mut data = base.clone();
+            data.as_slice_mut().unwrap()[idx] += delta;
+            let mut probe = inputs.to_vec();
+            probe[i] = Tensor::new(data);
+            let output = f(&probe).sum_all();
+            let value = output.data()[[]];
+            value
+        };
+
+        for (idx, &a) in analytic.iter().enumerate()
+        {
+            let n = (eval(idx, eps) - eval(idx, -eps)) / (2.0 * eps);
+            if (a - n).abs() > tol * (1.0 + n.abs())
+            {
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+///
+/// FUNCTIONAL UNIT TESTING
+///
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use ndarray::IxDyn;
+
+    fn scalar(value: f64) -> Tensor<f64>
+    {
+        let mut t = Tensor::new(ArrayD::from_elem(IxDyn(&[1]), value));
+        t.set_requires_grad(true);
+        t
+    }
+
+    #[test]
+    fn binary_ops()
+    {
+        let a = scalar(3.0);
+        let b = scalar(4.0);
+
+        // z = (a * b + a) / (a - b)
+        let c = a.mul(&b);
+        let d = c.add(&a);
+        let e = a.sub(&b);
+        let z = d.div(&e);
+        z.backward();
+
+        // dz/da = ((b + 1)(a - b) - (ab + a)) / (a - b)^2 = -20
+        // dz/db = (a(a - b) + (ab + a)) / (a - b)^2 = 12
+        assert_eq!(a.grad()[[0]], -20.0);
+        assert_eq!(b.grad()[[0]], 12.0);
+    }
+
+    #[test]
+    fn non_scalar_output()
+    {
+        let a = scalar(3.0).expand(&[2, 2]);
+
+        assert_eq!(
+            a.try_backward(),
+            Err(RuneError::invalid("backward", &[2, 2], "backward requires a scalar output".to_string())),
+        );
+    }
+
+    #[test]
+    fn broadcasting()
+    {
+        let mut a = Tensor::<f64>::ones(&[3, 1]);
+        let mut b = Tensor::new(
+            ArrayD::from_shape_vec(IxDyn(&[1, 2]), vec![2.0, 3.0]).unwrap()
+        );
+        let mut c = Tensor::<f64>::ones(&[1]);
+        a.set_requires_grad(true);
+        b.set_requires_grad(true);
+        c.set_requires_grad(true);
+
+        // [3, 1] * [1, 2] -> [3, 2], [3, 2] @ [2, 1] -> [3, 1], [1, 3] @ [3, 1] -> [1, 1]
+        let d = a.mul(&b).sub(&c).div(&c);
+        let e = d.matmul(&Tensor::ones(&[2, 1]));
+        let f = Tensor::ones(&[1, 3]).matmul(&e);
+        f.backward();
+
+        assert_eq!(a.grad().shape(), &[3, 1]);
+        assert_eq!(a.grad().as_slice().unwrap(), &[5.0, 5.0, 5.0]);
+        assert_eq!(b.grad().shape(), &[1, 2]);
+        assert_eq!(b.grad().as_slice().unwrap(), &[3.0, 3.0]);
+        assert_eq!(c.grad().shape(), &[1]);
+        // d(sum((ab - c) / c))/dc = sum(-ab / c^2) = -15
+        assert_eq!(c.grad()[[0]], -15.0);
+    }
+
+    #[test]
+    fn accumulates()
+    {
+        let a = scalar(2.0);
+        let b = a.mul(&a);
+        b.backward();
+        assert_eq!(a.grad()[[0]], 4.0);
+
+        b.backward();
+        assert_eq!(a.grad()[[0]], 8.0);
+
+        a.zero_grad();
+        assert_eq!(a.grad()[[0]], 0.0);
+    }
+
+    #[test]
+    fn matmul()
+    {
+        let mut a = Tensor::new(
+            ArrayD::from_shape_vec(IxDyn(&[1, 3]), vec![1.0, 2.0, 3.0]).unwrap()
+        );
+        let mut b = Tensor::new(
+            ArrayD::from_shape_vec(IxDyn(&[3, 1]), vec![4.0, 5.0, 6.0]).unwrap()
+        );
+        a.set_requires_grad(true);
+        b.set_requires_grad(true);
+
+        let c = a.matmul(&b);
+        c.backward();
+
+        assert_eq!(a.grad().as_slice().unwrap(), &[4.0, 5.0, 6.0]);
+        assert_eq!(b.grad().as_slice().unwrap(), &[1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn skips_constants()
+    {
+        let a = scalar(2.0);
+        let b = Tensor::new(ArrayD::from_elem(IxDyn(&[1]), 5.0));
+        let c = a.mul(&b);
+        c.backward();
+
+        assert_eq!(a.grad()[[0]], 5.0);
+        assert!(!b.requires_grad());
+    }
+}
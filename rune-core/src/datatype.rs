@@ -22,16 +22,23 @@
 // SOFTWARE.
 // 
 // File created: 2023-03-09
-// Last updated: 2023-03-09
+// Last updated: 2026-10-17
 //
 
 use std::fmt::Debug;
 use std::ops::AddAssign;
+use std::ops::DivAssign;
+use std::ops::MulAssign;
+use std::ops::SubAssign;
 
 use num_traits::Float;
 use num_traits::ToPrimitive;
 
-pub trait DataType: Default + Copy + Debug + Float + ToPrimitive + AddAssign {}
+pub trait DataType:
+    Default + Copy + Debug + Float + ToPrimitive
+    + AddAssign + SubAssign + MulAssign + DivAssign
+    + Send + Sync + 'static
+{}
 
 impl DataType for f32 {}
 impl DataType for f64 {}
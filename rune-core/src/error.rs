@@ -0,0 +1,154 @@
+//
+// MIT License
+// 
+// Copyright (c) 2023 Wilhelm Ågren
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// 
+// File created: 2026-10-17
+// Last updated: 2026-10-17
+//
+
+use std::error::Error;
+use std::fmt;
+
+///
+/// Everything that can go wrong when constructing tensors or running ops.
+/// Every op has a fallible `try_*` variant that returns this error, while
+/// the plain variant panics with its message.
+///
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RuneError
+{
+    ///
+    /// The shapes of the operands of an op are incompatible, e.g. the inner
+    /// dims of a matmul or shapes that do not broadcast.
+    ///
+    ShapeMismatch { op: &'static str, shapes: Vec<Vec<usize>> },
+    ///
+    /// An argument of an op does not fit the shape of its input, e.g. an 
+    /// axis that is out of bounds or a reshape into the wrong size.
+    ///
+    InvalidShape { op: &'static str, shape: Vec<usize>, reason: String },
+    IndexOutOfBounds { op: &'static str, index: usize, size: usize },
+    InvalidDistribution(String),
+    ///
+    /// An op that reads the raw memory of its input got data that is not
+    /// contiguous in standard layout.
+    ///
+    NonContiguous { op: &'static str },
+    ///
+    /// Data handed over from outside, e.g. a NumPy array, has another dtype
+    /// than the tensor it is read into.
+    ///
+    DTypeMismatch { expected: &'static str, found: String },
+    ///
+    /// A state dict lacks the entry for a parameter or buffer of a module.
+    ///
+    MissingKey { op: &'static str, key: String },
+}
+
+impl fmt::Display for RuneError
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+    {
+        match self
+        {
+            RuneError::ShapeMismatch { op, shapes } =>
+                write!(f, "{}: incompatible shapes {:?}", op, shapes),
+            RuneError::InvalidShape { op, shape, reason } =>
+                write!(f, "{}: {} for shape {:?}", op, reason, shape),
+            RuneError::IndexOutOfBounds { op, index, size } =>
+                write!(f, "{}: index {} is out of bounds for size {}", op, index, size),
+            RuneError::InvalidDistribution(reason) =>
+                write!(f, "invalid distribution parameters, {}", reason),
+            RuneError::NonContiguous { op } =>
+                write!(f, "{}: expected contiguous data in standard layout", op),
+            RuneError::DTypeMismatch { expected, found } =>
+                write!(f, "expected dtype {} but got {}", expected, found),
+            RuneError::MissingKey { op, key } =>
+                write!(f, "{}: missing key {}", op, key),
+        }
+    }
+}
+
+impl Error for RuneError {}
+
+pub type Result<V> = std::result::Result<V, RuneError>;
+
+impl RuneError
+{
+    pub(crate) fn shapes(op: &'static str, shapes: &[&[usize]]) -> Self
+    {
+        RuneError::ShapeMismatch { op, shapes: shapes.iter().map(|s| s.to_vec()).collect() }
+    }
+
+    pub(crate) fn invalid(op: &'static str, shape: &[usize], reason: String) -> Self
+    {
+        RuneError::InvalidShape { op, shape: shape.to_vec(), reason }
+    }
+}
+
+///
+/// Unwrap the result of a `try_*` op, panicking with the error message at
+/// the location of the caller.
+///
+#[track_caller]
+pub(crate) fn unwrap<V>(result: Result<V>) -> V
+{
+    match result
+    {
+        Ok(value) => value,
+        Err(e) => panic!("{}", e),
+    }
+}
+
+///
+/// FUNCTIONAL UNIT TESTING
+///
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn display()
+    {
+        let a = RuneError::shapes("matmul", &[&[2, 3], &[2, 3]]);
+        let b = RuneError::invalid("squeeze", &[32, 16], "axis 1 does not have size 1".to_string());
+        let c = RuneError::InvalidDistribution("low must be less than high".to_string());
+        let d = RuneError::MissingKey { op: "load_state_dict", key: "0.weight".to_string() };
+        let e = RuneError::NonContiguous { op: "conv2d" };
+        let f = RuneError::DTypeMismatch { expected: "float32", found: "float64".to_string() };
+
+        assert_eq!(a.to_string(), "matmul: incompatible shapes [[2, 3], [2, 3]]");
+        assert_eq!(b.to_string(), "squeeze: axis 1 does not have size 1 for shape [32, 16]");
+        assert_eq!(c.to_string(), "invalid distribution parameters, low must be less than high");
+        assert_eq!(d.to_string(), "load_state_dict: missing key 0.weight");
+        assert_eq!(e.to_string(), "conv2d: expected contiguous data in standard layout");
+        assert_eq!(f.to_string(), "expected dtype float32 but got float64");
+    }
+
+    #[test]
+    #[should_panic(expected = "index 3 is out of bounds for size 2")]
+    fn unwrap_panics()
+    {
+        unwrap::<()>(Err(RuneError::IndexOutOfBounds { op: "gather", index: 3, size: 2 }));
+    }
+}
@@ -22,11 +22,16 @@
 // SOFTWARE.
 // 
 // File created: 2023-03-09
-// Last updated: 2023-03-12
+// Last updated: 2026-10-17
 //
 
+pub mod autograd;
 pub mod datatype;
+pub mod error;
 pub mod nn;
+pub mod ops;
+pub mod optim;
+pub mod random;
 pub mod shape;
 pub mod tensor;
 pub mod utils;
@@ -35,17 +40,23 @@ pub mod utils;
 mod tests
 {
     use crate::tensor::Tensor;
-    use crate::nn::module::Module;
 
     #[test]
     fn backward()
     {
         let x = Tensor::<f32>::ones(&[128, 128]);
-        let w = Tensor::<f32>::uniform(&[128, 500], -1.0, 1.0);
-        let b = Tensor::<f32>::uniform(&[1, 500], -1.0, 1.0);
+        let mut w = Tensor::<f32>::uniform(&[128, 500], -1.0, 1.0);
+        let mut b = Tensor::<f32>::uniform(&[1, 500], -1.0, 1.0);
+        w.set_requires_grad(true);
+        b.set_requires_grad(true);
+
         let r = x.matmul(&w);
         println!("{:?} = {:?} @ {:?}", r.shape().dims(), x.shape().dims(), w.shape().dims());
         let y = r.add(&b);
+        y.sum_all().backward();
+
+        assert!(w.grad().iter().all(|&g| g == 128.0));
+        assert!(b.grad().iter().all(|&g| g == 128.0));
     }
 }
 
@@ -0,0 +1,437 @@
+//
+// MIT License
+// 
+// Copyright (c) 2023 Wilhelm Ågren
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// 
+// File created: 2026-10-17
+// Last updated: 2026-10-17
+//
+
+use crate::datatype::DataType;
+use crate::error::unwrap;
+use crate::nn::module::Module;
+use crate::nn::parameter::Parameter;
+use crate::random::with_global_generator;
+use crate::random::Generator;
+use crate::tensor::Tensor;
+
+///
+/// A convolution over N spatial dims, see `Tensor::conv2d`. The weight is
+/// stored as [out_channels, in_channels / groups, *kernel] and the bias as
+/// [out_channels]. Both are initialized from U(-k, k) where k = 1 /
+/// sqrt(fan_in) and fan_in = in_channels / groups * prod(kernel).
+///
+/// Stride, padding and dilation default to 1, 0 and 1 and are set with the
+/// builder methods, since they do not change the shape of the parameters.
+///
+/// # Example
+///
+/// let conv = Conv2d::<f32>::new(3, 16, [3, 3], 1, true).stride([2, 2]).padding([1, 1]);
+/// let y = conv.forward(&Tensor::ones(&[8, 3, 32, 32]));
+///
+/// >>> y.shape() = Shape { dims: [8, 16, 16, 16] }
+///
+pub struct Conv<T: DataType, const N: usize>
+{
+    weight: Tensor<T>,
+    bias: Option<Tensor<T>>,
+    stride: [usize; N],
+    padding: [usize; N],
+    dilation: [usize; N],
+    groups: usize,
+    training: bool,
+}
+
+pub type Conv1d<T> = Conv<T, 1>;
+pub type Conv2d<T> = Conv<T, 2>;
+pub type Conv3d<T> = Conv<T, 3>;
+
+impl<T: DataType, const N: usize> Conv<T, N>
+{
+    pub fn new(in_channels: usize, out_channels: usize, kernel: [usize; N], groups: usize, bias: bool) -> Self
+    {
+        with_global_generator(|g| Conv::new_with(in_channels, out_channels, kernel, groups, bias, g))
+    }
+
+    pub fn new_with(
+        in_channels: usize,
+        out_channels: usize,
+        kernel: [usize; N],
+        groups: usize,
+        bias: bool,
+        generator: &mut Generator,
+    ) -> Self
+    {
+        assert!(
+            groups > 0 && in_channels.is_multiple_of(groups) && out_channels.is_multiple_of(groups),
+            "Channels {} -> {} are not divisible by {} groups", in_channels, out_channels, groups,
+        );
+
+        let mut dims = vec![out_channels, in_channels / groups];
+        dims.extend(kernel);
+        let fan_in: usize = dims[1..].iter().product();
+        let k = 1.0 / (fan_in as f32).sqrt();
+        let weight = Parameter::uniform_with(&dims, -k, k, generator);
+        let bias = match bias
+        {
+            true => Some(Parameter::uniform_with(&[out_channels], -k, k, generator)),
+            false => None,
+        };
+        Conv { weight, bias, stride: [1; N], padding: [0; N], dilation: [1; N], groups, training: true }
+    }
+
+    pub fn stride(mut self, stride: [usize; N]) -> Self
+    {
+        self.stride = stride;
+        self
+    }
+
+    pub fn padding(mut self, padding: [usize; N]) -> Self
+    {
+        self.padding = padding;
+        self
+    }
+
+    pub fn dilation(mut self, dilation: [usize; N]) -> Self
+    {
+        self.dilation = dilation;
+        self
+    }
+
+    pub fn weight(&self) -> &Tensor<T>
+    {
+        &self.weight
+    }
+
+    pub fn bias(&self) -> Option<&Tensor<T>>
+    {
+        self.bias.as_ref()
+    }
+
+    pub fn in_channels(&self) -> usize
+    {
+        self.weight.shape().dims()[1] * self.groups
+    }
+
+    pub fn out_channels(&self) -> usize
+    {
+        self.weight.shape().dims()[0]
+    }
+
+    pub fn groups(&self) -> usize
+    {
+        self.groups
+    }
+}
+
+impl<T: DataType, const N: usize> Module<T> for Conv<T, N>
+{
+    fn forward(&self, input: &Tensor<T>) -> Tensor<T>
+    {
+        unwrap(input.try_conv(
+            &self.weight, self.bias.as_ref(), self.stride, self.padding, self.dilation, self.groups,
+        ))
+    }
+
+    fn named_parameters(&self) -> Vec<(String, Tensor<T>)>
+    {
+        let mut parameters = vec![("weight".to_string(), self.weight.clone())];
+        if let Some(bias) = &self.bias
+        {
+            parameters.push(("bias".to_string(), bias.clone()));
+        }
+        parameters
+    }
+
+    fn parameters_mut(&mut self) -> Vec<&mut Tensor<T>>
+    {
+        let mut parameters = vec![&mut self.weight];
+        if let Some(bias) = &mut self.bias
+        {
+            parameters.push(bias);
+        }
+        parameters
+    }
+
+    fn set_training(&mut self, training: bool)
+    {
+        self.training = training;
+    }
+
+    fn is_training(&self) -> bool
+    {
+        self.training
+    }
+}
+
+///
+/// A transposed convolution over N spatial dims, see
+/// `Tensor::conv_transpose2d`. The weight is stored as [in_channels,
+/// out_channels / groups, *kernel] and is initialized like the weight of
+/// `Conv`, with fan_in = out_channels / groups * prod(kernel).
+///
+/// # Example
+///
+/// let up = ConvTranspose2d::<f32>::new(16, 8, [3, 3], 1, true).stride([2, 2]).padding([1, 1]).output_padding([1, 1]);
+/// let y = up.forward(&Tensor::ones(&[4, 16, 16, 16]));
+///
+/// >>> y.shape() = Shape { dims: [4, 8, 32, 32] }
+///
+pub struct ConvTranspose<T: DataType, const N: usize>
+{
+    weight: Tensor<T>,
+    bias: Option<Tensor<T>>,
+    stride: [usize; N],
+    padding: [usize; N],
+    output_padding: [usize; N],
+    dilation: [usize; N],
+    groups: usize,
+    training: bool,
+}
+
+pub type ConvTranspose1d<T> = ConvTranspose<T, 1>;
+pub type ConvTranspose2d<T> = ConvTranspose<T, 2>;
+
+impl<T: DataType, const N: usize> ConvTranspose<T, N>
+{
+    pub fn new(in_channels: usize, out_channels: usize, kernel: [usize; N], groups: usize, bias: bool) -> Self
+    {
+        with_global_generator(|g| ConvTranspose::new_with(in_channels, out_channels, kernel, groups, bias, g))
+    }
+
+    pub fn new_with(
+        in_channels: usize,
+        out_channels: usize,
+        kernel: [usize; N],
+        groups: usize,
+        bias: bool,
+        generator: &mut Generator,
+    ) -> Self
+    {
+        assert!(
+            groups > 0 && in_channels.is_multiple_of(groups) && out_channels.is_multiple_of(groups),
+            "Channels {} -> {} are not divisible by {} groups", in_channels, out_channels, groups,
+        );
+
+        let mut dims = vec![in_channels, out_channels / groups];
+        dims.extend(kernel);
+        let fan_in: usize = dims[1..].iter().product();
+        let k = 1.0 / (fan_in as f32).sqrt();
+        let weight = Parameter::uniform_with(&dims, -k, k, generator);
+        let bias = match bias
+        {
+            true => Some(Parameter::uniform_with(&[out_channels], -k, k, generator)),
+            false => None,
+        };
+        ConvTranspose {
+            weight,
+            bias,
+            stride: [1; N],
+            padding: [0; N],
+            output_padding: [0; N],
+            dilation: [1; N],
+            groups,
+            training: true,
+        }
+    }
+
+    pub fn stride(mut self, stride: [usize; N]) -> Self
+    {
+        self.stride = stride;
+        self
+    }
+
+    pub fn padding(mut self, padding: [usize; N]) -> Self
+    {
+        self.padding = padding;
+        self
+    }
+
+    pub fn output_padding(mut self, output_padding: [usize; N]) -> Self
+    {
+        self.output_padding = output_padding;
+        self
+    }
+
+    pub fn dilation(mut self, dilation: [usize; N]) -> Self
+    {
+        self.dilation = dilation;
+        self
+    }
+
+    pub fn weight(&self) -> &Tensor<T>
+    {
+        &self.weight
+    }
+
+    pub fn bias(&self) -> Option<&Tensor<T>>
+    {
+        self.bias.as_ref()
+    }
+
+    pub fn in_channels(&self) -> usize
+    {
+        self.weight.shape().dims()[0]
+    }
+
+    pub fn out_channels(&self) -> usize
+    {
+        self.weight.shape().dims()[1] * self.groups
+    }
+
+    pub fn groups(&self) -> usize
+    {
+        self.groups
+    }
+}
+
+impl<T: DataType, const N: usize> Module<T> for ConvTranspose<T, N>
+{
+    fn forward(&self, input: &Tensor<T>) -> Tensor<T>
+    {
+        unwrap(input.try_conv_transpose(
+            &self.weight,
+            self.bias.as_ref(),
+            self.stride,
+            self.padding,
+            self.output_padding,
+            self.dilation,
+            self.groups,
+        ))
+    }
+
+    fn named_parameters(&self) -> Vec<(String, Tensor<T>)>
+    {
+        let mut parameters = vec![("weight".to_string(), self.weight.clone())];
+        if let Some(bias) = &self.bias
+        {
+            parameters.push(("bias".to_string(), bias.clone()));
+        }
+        parameters
+    }
+
+    fn parameters_mut(&mut self) -> Vec<&mut Tensor<T>>
+    {
+        let mut parameters = vec![&mut self.weight];
+        if let Some(bias) = &mut self.bias
+        {
+            parameters.push(bias);
+        }
+        parameters
+    }
+
+    fn set_training(&mut self, training: bool)
+    {
+        self.training = training;
+    }
+
+    fn is_training(&self) -> bool
+    {
+        self.training
+    }
+}
+
+///
+/// FUNCTIONAL UNIT TESTING
+///
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::autograd::gradcheck;
+
+    #[test]
+    fn shapes()
+    {
+        let conv = Conv2d::<f32>::new(3, 16, [3, 3], 1, true).stride([2, 2]).padding([1, 1]);
+        assert_eq!(*conv.forward(&Tensor::ones(&[8, 3, 32, 32])).shape().dims(), vec![8, 16, 16, 16]);
+        assert_eq!(*conv.weight().shape().dims(), vec![16, 3, 3, 3]);
+
+        let conv = Conv1d::<f32>::new(4, 6, [5], 2, false).dilation([2]);
+        assert_eq!(*conv.forward(&Tensor::ones(&[4, 20])).shape().dims(), vec![6, 12]);
+        assert_eq!(*conv.weight().shape().dims(), vec![6, 2, 5]);
+        assert_eq!((conv.in_channels(), conv.out_channels(), conv.groups()), (4, 6, 2));
+
+        let conv = Conv3d::<f32>::new(2, 4, [3, 3, 3], 1, true).padding([1, 1, 1]);
+        assert_eq!(*conv.forward(&Tensor::ones(&[1, 2, 4, 5, 6])).shape().dims(), vec![1, 4, 4, 5, 6]);
+    }
+
+    #[test]
+    fn initialization()
+    {
+        let conv = Conv2d::<f64>::new(8, 4, [5, 5], 2, true);
+        let k = 1.0 / 10.0 + 1e-6;
+
+        assert!(conv.weight().data().iter().all(|w| w.abs() <= k));
+        assert!(conv.bias().unwrap().data().iter().all(|b| b.abs() <= k));
+        assert_eq!(conv.named_parameters()[1].0, "bias");
+
+        let a = Conv1d::<f32>::new_with(2, 3, [3], 1, false, &mut Generator::new(0));
+        let b = Conv1d::<f32>::new_with(2, 3, [3], 1, false, &mut Generator::new(0));
+        assert_eq!(*a.weight().data(), *b.weight().data());
+        assert!(a.bias().is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn indivisible_groups()
+    {
+        Conv2d::<f32>::new(4, 6, [3, 3], 4, true);
+    }
+
+    #[test]
+    fn gradients()
+    {
+        let conv = Conv2d::<f64>::new(2, 3, [2, 2], 1, true).stride([2, 1]).padding([1, 0]);
+        let mut x = Tensor::<f64>::uniform(&[2, 2, 4, 3], -1.0, 1.0);
+        x.set_requires_grad(true);
+
+        assert!(gradcheck(|t| conv.forward(&t[0]), &[x], 1e-3, 1e-6));
+        assert_eq!(*conv.weight().grad().shape(), [3, 2, 2, 2]);
+        assert!(conv.bias().unwrap().grad().iter().all(|&g| g == 12.0));
+    }
+
+    #[test]
+    fn transpose_shapes()
+    {
+        let up = ConvTranspose2d::<f32>::new(16, 8, [3, 3], 1, true).stride([2, 2]).padding([1, 1]).output_padding([1, 1]);
+        assert_eq!(*up.forward(&Tensor::ones(&[4, 16, 16, 16])).shape().dims(), vec![4, 8, 32, 32]);
+        assert_eq!(*up.weight().shape().dims(), vec![16, 8, 3, 3]);
+
+        let up = ConvTranspose1d::<f32>::new(4, 6, [4], 2, false).stride([2]).dilation([2]);
+        assert_eq!(*up.forward(&Tensor::ones(&[4, 10])).shape().dims(), vec![6, 25]);
+        assert_eq!(*up.weight().shape().dims(), vec![4, 3, 4]);
+        assert_eq!((up.in_channels(), up.out_channels(), up.groups()), (4, 6, 2));
+        assert!(up.bias().is_none());
+    }
+
+    #[test]
+    fn transpose_gradients()
+    {
+        let up = ConvTranspose1d::<f64>::new(2, 3, [3], 1, true).stride([2]).padding([1]).output_padding([1]);
+        let mut x = Tensor::<f64>::uniform(&[2, 2, 4], -1.0, 1.0);
+        x.set_requires_grad(true);
+
+        assert!(gradcheck(|t| up.forward(&t[0]), &[x], 1e-3, 1e-6));
+        assert_eq!(*up.weight().grad().shape(), [2, 3, 3]);
+        assert!(up.bias().unwrap().grad().iter().all(|&g| g == 16.0));
+    }
+}
@@ -0,0 +1,421 @@
+//
+// MIT License
+// 
+// Copyright (c) 2023 Wilhelm Ågren
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// 
+// File created: 2026-10-17
+// Last updated: 2026-10-17
+//
+
+use crate::datatype::DataType;
+use crate::nn::module::Module;
+use crate::random::with_global_generator;
+use crate::random::Generator;
+use crate::tensor::Tensor;
+
+use ndarray::ArrayD;
+use ndarray::IxDyn;
+
+use ndarray_rand::RandomExt;
+use ndarray_rand::rand_distr::Bernoulli;
+
+use std::cell::RefCell;
+use std::marker::PhantomData;
+
+///
+/// The negative saturation value of SELU, -lambda * alpha, which alpha
+/// dropout sets dropped units to.
+///
+const SELU_SATURATION: f64 = -1.758_099_340_847_376_6;
+
+///
+/// Zeroes every element of the input with probability `p` during training
+/// and scales the kept ones by 1 / (1 - p), so that the expected output is
+/// the input and nothing has to change at evaluation, where dropout is the
+/// identity. The mask is part of the graph, so backward only passes the
+/// gradient of the kept elements, scaled the same way.
+///
+/// Every dropout module draws its masks from its own generator, forked from
+/// the global generator by `new` or from a given one by `new_with`.
+///
+/// # Example
+///
+/// let mut dropout = Dropout::<f32>::new(0.5);
+/// let y = dropout.forward(&Tensor::ones(&[2, 4]));
+/// dropout.eval();
+///
+/// >>> y.data() = [[2.0, 0.0, 2.0, 2.0], [0.0, 0.0, 2.0, 0.0]]
+/// >>> dropout.forward(&Tensor::ones(&[2, 4])).data() = [[1.0, 1.0, 1.0, 1.0], [1.0, 1.0, 1.0, 1.0]]
+///
+pub struct Dropout<T: DataType>
+{
+    p: f64,
+    generator: RefCell<Generator>,
+    training: bool,
+    dtype: PhantomData<T>,
+}
+
+impl<T: DataType> Dropout<T>
+{
+    pub fn new(p: f64) -> Self
+    {
+        with_global_generator(|g| Dropout::new_with(p, g))
+    }
+
+    pub fn new_with(p: f64, generator: &mut Generator) -> Self
+    {
+        check_probability(p);
+        Dropout { p, generator: RefCell::new(generator.fork()), training: true, dtype: PhantomData }
+    }
+
+    pub fn p(&self) -> f64
+    {
+        self.p
+    }
+}
+
+impl<T: DataType> Module<T> for Dropout<T>
+{
+    fn forward(&self, input: &Tensor<T>) -> Tensor<T>
+    {
+        if !self.training || self.p == 0.0
+        {
+            return input.clone();
+        }
+
+        let mask = inverted_mask(input.shape().dims(), self.p, &mut self.generator.borrow_mut());
+        input.mul(&Tensor::new(mask))
+    }
+
+    fn named_parameters(&self) -> Vec<(String, Tensor<T>)>
+    {
+        Vec::new()
+    }
+
+    fn parameters_mut(&mut self) -> Vec<&mut Tensor<T>>
+    {
+        Vec::new()
+    }
+
+    fn set_training(&mut self, training: bool)
+    {
+        self.training = training;
+    }
+
+    fn is_training(&self) -> bool
+    {
+        self.training
+    }
+}
+
+///
+/// Dropout of whole channels of inputs of [batch, channels, height, width],
+/// or [channels, height, width] without the batch dim. Neighbouring pixels
+/// of a feature map are strongly correlated, so dropping them one by one
+/// barely regularizes conv layers, while dropping entire maps does.
+///
+pub struct Dropout2d<T: DataType>
+{
+    p: f64,
+    generator: RefCell<Generator>,
+    training: bool,
+    dtype: PhantomData<T>,
+}
+
+impl<T: DataType> Dropout2d<T>
+{
+    pub fn new(p: f64) -> Self
+    {
+        with_global_generator(|g| Dropout2d::new_with(p, g))
+    }
+
+    pub fn new_with(p: f64, generator: &mut Generator) -> Self
+    {
+        check_probability(p);
+        Dropout2d { p, generator: RefCell::new(generator.fork()), training: true, dtype: PhantomData }
+    }
+
+    pub fn p(&self) -> f64
+    {
+        self.p
+    }
+}
+
+impl<T: DataType> Module<T> for Dropout2d<T>
+{
+    fn forward(&self, input: &Tensor<T>) -> Tensor<T>
+    {
+        let dims = input.shape().dims();
+        assert!(
+            dims.len() == 3 || dims.len() == 4,
+            "Dropout2d expects an input of 3 or 4 dims, got {:?}", dims,
+        );
+        if !self.training || self.p == 0.0
+        {
+            return input.clone();
+        }
+
+        let mut channels = dims[..dims.len() - 2].to_vec();
+        channels.extend([1, 1]);
+        let mask = inverted_mask(&channels, self.p, &mut self.generator.borrow_mut());
+        input.mul(&Tensor::new(mask))
+    }
+
+    fn named_parameters(&self) -> Vec<(String, Tensor<T>)>
+    {
+        Vec::new()
+    }
+
+    fn parameters_mut(&mut self) -> Vec<&mut Tensor<T>>
+    {
+        Vec::new()
+    }
+
+    fn set_training(&mut self, training: bool)
+    {
+        self.training = training;
+    }
+
+    fn is_training(&self) -> bool
+    {
+        self.training
+    }
+}
+
+///
+/// Dropout for self-normalizing networks with SELU activations. Dropped
+/// elements are set to the negative saturation value of SELU instead of
+/// zero, and the output is transformed as a * y + b, which keeps inputs of
+/// zero mean and unit variance at zero mean and unit variance.
+///
+pub struct AlphaDropout<T: DataType>
+{
+    p: f64,
+    generator: RefCell<Generator>,
+    training: bool,
+    dtype: PhantomData<T>,
+}
+
+impl<T: DataType> AlphaDropout<T>
+{
+    pub fn new(p: f64) -> Self
+    {
+        with_global_generator(|g| AlphaDropout::new_with(p, g))
+    }
+
+    pub fn new_with(p: f64, generator: &mut Generator) -> Self
+    {
+        check_probability(p);
+        AlphaDropout { p, generator: RefCell::new(generator.fork()), training: true, dtype: PhantomData }
+    }
+
+    pub fn p(&self) -> f64
+    {
+        self.p
+    }
+}
+
+impl<T: DataType> Module<T> for AlphaDropout<T>
+{
+    ///
+    /// With keep probability q, a = (q + saturation^2 * q * p)^-1/2 and
+    /// b = -a * saturation * p, kept elements become a * x + b and dropped
+    /// ones a * saturation + b. With p = 1 everything is dropped to zero.
+    ///
+    fn forward(&self, input: &Tensor<T>) -> Tensor<T>
+    {
+        if !self.training || self.p == 0.0
+        {
+            return input.clone();
+        }
+        if self.p == 1.0
+        {
+            return input.mul(&Tensor::new(ArrayD::zeros(IxDyn(input.shape().dims()))));
+        }
+
+        let (p, q) = (self.p, 1.0 - self.p);
+        let a = (q + SELU_SATURATION * SELU_SATURATION * q * p).powf(-0.5);
+        let b = -a * SELU_SATURATION * p;
+        let keep = ArrayD::random_using(
+            IxDyn(input.shape().dims()),
+            Bernoulli::new(q).unwrap(),
+            &mut *self.generator.borrow_mut(),
+        );
+        let scale = keep.mapv(|k| T::from(if k { a } else { 0.0 }).unwrap());
+        let shift = keep.mapv(|k| T::from(if k { b } else { a * SELU_SATURATION + b }).unwrap());
+        input.mul(&Tensor::new(scale)).add(&Tensor::new(shift))
+    }
+
+    fn named_parameters(&self) -> Vec<(String, Tensor<T>)>
+    {
+        Vec::new()
+    }
+
+    fn parameters_mut(&mut self) -> Vec<&mut Tensor<T>>
+    {
+        Vec::new()
+    }
+
+    fn set_training(&mut self, training: bool)
+    {
+        self.training = training;
+    }
+
+    fn is_training(&self) -> bool
+    {
+        self.training
+    }
+}
+
+fn check_probability(p: f64)
+{
+    assert!((0.0..=1.0).contains(&p), "Dropout probability has to be in [0, 1], got {}", p);
+}
+
+///
+/// A mask of 1 / (1 - p) for kept and 0 for dropped elements. With p = 1
+/// everything is dropped.
+///
+fn inverted_mask<T: DataType>(dims: &[usize], p: f64, generator: &mut Generator) -> ArrayD<T>
+{
+    let q = 1.0 - p;
+    let scale = match q > 0.0
+    {
+        true => T::from(1.0 / q).unwrap(),
+        false => T::zero(),
+    };
+    ArrayD::random_using(IxDyn(dims), Bernoulli::new(q).unwrap(), generator)
+        .mapv(|keep| if keep { scale } else { T::zero() })
+}
+
+///
+/// FUNCTIONAL UNIT TESTING
+///
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::nn::linear::Linear;
+    use crate::nn::sequential::Sequential;
+
+    fn input(dims: &[usize]) -> Tensor<f64>
+    {
+        let mut t = Tensor::uniform_with(dims, 1.0, 2.0, &mut Generator::new(0));
+        t.set_requires_grad(true);
+        t
+    }
+
+    #[test]
+    fn dropout()
+    {
+        let x = input(&[100, 100]);
+        let dropout = Dropout::new_with(0.3, &mut Generator::new(1));
+        let y = dropout.forward(&x);
+
+        let kept = y.data().iter().filter(|&&v| v != 0.0).count() as f64 / 10000.0;
+        assert!((kept - 0.7).abs() < 0.02);
+        assert!(y.data().iter().zip(x.data().iter()).all(|(&y, &x)| y == 0.0 || (y - x / 0.7).abs() < 1e-12));
+
+        y.sum_all().backward();
+        assert!(x.grad().iter().zip(y.data().iter()).all(|(&g, &y)| match y == 0.0
+        {
+            true => g == 0.0,
+            false => (g - 1.0 / 0.7).abs() < 1e-12,
+        }));
+    }
+
+    #[test]
+    fn identity()
+    {
+        let x = input(&[4, 5]);
+        let mut dropout = Dropout::new(0.5);
+        dropout.eval();
+        assert_eq!(*dropout.forward(&x).data(), *x.data());
+        assert_eq!(*Dropout::new(0.0).forward(&x).data(), *x.data());
+        assert!(Dropout::new(1.0).forward(&x).data().iter().all(|&v| v == 0.0));
+        assert!(AlphaDropout::new(1.0).forward(&x).data().iter().all(|&v| v == 0.0));
+
+        let mut alpha = AlphaDropout::new(0.5);
+        alpha.eval();
+        assert_eq!(*alpha.forward(&x).data(), *x.data());
+
+        let mut model = Sequential::<f64>::new();
+        model.push(Linear::new(5, 5, true));
+        model.push(Dropout2d::new(0.9));
+        model.eval();
+        assert!(!model[1].is_training());
+    }
+
+    #[test]
+    fn seeded()
+    {
+        let x = input(&[8, 8]);
+        let a = Dropout::new_with(0.5, &mut Generator::new(2));
+        let b = Dropout::new_with(0.5, &mut Generator::new(2));
+
+        let first = a.forward(&x).data().clone();
+        assert_eq!(first, *b.forward(&x).data());
+        assert_ne!(first, *a.forward(&x).data());
+    }
+
+    #[test]
+    fn channels()
+    {
+        let x = input(&[4, 16, 3, 3]);
+        let y = Dropout2d::new_with(0.5, &mut Generator::new(3)).forward(&x);
+
+        let mut dropped = 0;
+        let (y, x) = (y.data(), x.data());
+        for (y_c, x_c) in y.exact_chunks(IxDyn(&[1, 1, 3, 3])).into_iter().zip(x.exact_chunks(IxDyn(&[1, 1, 3, 3])))
+        {
+            match y_c.iter().all(|&v| v == 0.0)
+            {
+                true => dropped += 1,
+                false => assert!(y_c.iter().zip(x_c.iter()).all(|(&y, &x)| (y - 2.0 * x).abs() < 1e-12)),
+            }
+        }
+        assert!(dropped > 16 && dropped < 48);
+
+        let y = Dropout2d::new(0.5).forward(&input(&[16, 3, 3]));
+        assert_eq!(*y.shape().dims(), vec![16, 3, 3]);
+    }
+
+    #[test]
+    fn self_normalizing()
+    {
+        let mut x = Tensor::<f64>::normal_with(&[200, 200], 0.0, 1.0, &mut Generator::new(4));
+        x.set_requires_grad(true);
+        let y = AlphaDropout::new_with(0.2, &mut Generator::new(5)).forward(&x);
+
+        let data = y.data();
+        let mean = data.mean().unwrap();
+        let std = data.mapv(|v| (v - mean).powi(2)).mean().unwrap().sqrt();
+        assert!(mean.abs() < 0.02);
+        assert!((std - 1.0).abs() < 0.02);
+
+        y.sum_all().backward();
+        let grad = x.grad();
+        let a = grad.fold(0.0, |m: f64, &g| m.max(g));
+        assert!((a - (0.8 + SELU_SATURATION.powi(2) * 0.16).powf(-0.5)).abs() < 1e-12);
+        assert!(grad.iter().all(|&g| g == 0.0 || g == a));
+        assert!(grad.iter().any(|&g| g == 0.0));
+    }
+}
@@ -0,0 +1,237 @@
+//
+// MIT License
+// 
+// Copyright (c) 2023 Wilhelm Ågren
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// 
+// File created: 2026-10-17
+// Last updated: 2026-10-17
+//
+
+use crate::datatype::DataType;
+use crate::nn::module::Module;
+use crate::nn::parameter::Parameter;
+use crate::random::with_global_generator;
+use crate::random::Generator;
+use crate::tensor::Tensor;
+
+///
+/// A fully connected layer y = x @ W + b, with the weight stored as 
+/// [fan_in, fan_out] and the bias as [fan_out] so that it broadcasts over
+/// the rows of the output. Both are initialized from U(-k, k) where 
+/// k = 1 / sqrt(fan_in).
+///
+/// The input can have any number of leading batch dims, only the last dim
+/// must be `fan_in`.
+///
+/// # Example
+///
+/// let linear = Linear::<f32>::new(64, 10, true);
+/// let y = linear.forward(&Tensor::ones(&[32, 8, 64]));
+///
+/// >>> y.shape() = Shape { dims: [32, 8, 10] }
+///
+pub struct Linear<T: DataType>
+{
+    weight: Tensor<T>,
+    bias: Option<Tensor<T>>,
+    training: bool,
+}
+
+impl<T: DataType> Linear<T>
+{
+    pub fn new(fan_in: usize, fan_out: usize, bias: bool) -> Self
+    {
+        with_global_generator(|g| Linear::new_with(fan_in, fan_out, bias, g))
+    }
+
+    pub fn new_with(fan_in: usize, fan_out: usize, bias: bool, generator: &mut Generator) -> Self
+    {
+        let k = 1.0 / (fan_in as f32).sqrt();
+        let weight = Parameter::uniform_with(&[fan_in, fan_out], -k, k, generator);
+        let bias = match bias
+        {
+            true => Some(Parameter::uniform_with(&[fan_out], -k, k, generator)),
+            false => None,
+        };
+        Linear { weight, bias, training: true }
+    }
+
+    pub fn weight(&self) -> &Tensor<T>
+    {
+        &self.weight
+    }
+
+    pub fn bias(&self) -> Option<&Tensor<T>>
+    {
+        self.bias.as_ref()
+    }
+
+    pub fn fan_in(&self) -> usize
+    {
+        self.weight.shape().dims()[0]
+    }
+
+    pub fn fan_out(&self) -> usize
+    {
+        self.weight.shape().dims()[1]
+    }
+}
+
+impl<T: DataType> Module<T> for Linear<T>
+{
+    ///
+    /// Leading batch dims are flattened into the rows of a single matrix 
+    /// product, instead of one product per batch entry.
+    ///
+    fn forward(&self, input: &Tensor<T>) -> Tensor<T>
+    {
+        let dims = input.shape().dims();
+        let output = match dims.len()
+        {
+            0..=2 => input.matmul(&self.weight),
+            _ =>
+            {
+                let mut out: Vec<isize> = dims[..dims.len() - 1].iter().map(|&d| d as isize).collect();
+                out.push(self.fan_out() as isize);
+                input.reshape(&[-1, self.fan_in() as isize])
+                    .matmul(&self.weight)
+                    .reshape(&out)
+            },
+        };
+
+        match &self.bias
+        {
+            Some(bias) => output.add(bias),
+            None => output,
+        }
+    }
+
+    fn named_parameters(&self) -> Vec<(String, Tensor<T>)>
+    {
+        let mut parameters = vec![("weight".to_string(), self.weight.clone())];
+        if let Some(bias) = &self.bias
+        {
+            parameters.push(("bias".to_string(), bias.clone()));
+        }
+        parameters
+    }
+
+    fn parameters_mut(&mut self) -> Vec<&mut Tensor<T>>
+    {
+        let mut parameters = vec![&mut self.weight];
+        if let Some(bias) = &mut self.bias
+        {
+            parameters.push(bias);
+        }
+        parameters
+    }
+
+    fn set_training(&mut self, training: bool)
+    {
+        self.training = training;
+    }
+
+    fn is_training(&self) -> bool
+    {
+        self.training
+    }
+}
+
+///
+/// FUNCTIONAL UNIT TESTING
+///
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::autograd::gradcheck;
+
+    #[test]
+    fn shapes()
+    {
+        let linear = Linear::<f32>::new(64, 10, true);
+
+        assert_eq!(*linear.forward(&Tensor::ones(&[64])).shape().dims(), vec![10]);
+        assert_eq!(*linear.forward(&Tensor::ones(&[32, 64])).shape().dims(), vec![32, 10]);
+        assert_eq!(*linear.forward(&Tensor::ones(&[4, 8, 2, 64])).shape().dims(), vec![4, 8, 2, 10]);
+        assert_eq!(*linear.bias().unwrap().shape().dims(), vec![10]);
+    }
+
+    #[test]
+    fn initialization()
+    {
+        let linear = Linear::<f64>::new(100, 50, true);
+        let k = 1.0 / 10.0 + 1e-6;
+
+        assert!(linear.weight().data().iter().all(|w| w.abs() <= k));
+        assert!(linear.bias().unwrap().data().iter().all(|b| b.abs() <= k));
+        assert!(linear.weight().requires_grad());
+
+        let a = Linear::<f32>::new_with(4, 3, true, &mut Generator::new(0));
+        let b = Linear::<f32>::new_with(4, 3, true, &mut Generator::new(0));
+        assert_eq!(*a.weight().data(), *b.weight().data());
+        assert_eq!(*a.bias().unwrap().data(), *b.bias().unwrap().data());
+    }
+
+    #[test]
+    fn without_bias()
+    {
+        let mut linear = Linear::<f32>::new(3, 2, false);
+
+        assert!(linear.bias().is_none());
+        assert_eq!(linear.parameters().len(), 1);
+        assert_eq!(linear.parameters_mut().len(), 1);
+        assert_eq!(linear.named_parameters()[0].0, "weight");
+    }
+
+    #[test]
+    fn modes()
+    {
+        let mut linear = Linear::<f32>::new(3, 2, true);
+        assert!(linear.is_training());
+
+        linear.eval();
+        assert!(!linear.is_training());
+
+        linear.train();
+        assert!(linear.is_training());
+    }
+
+    #[test]
+    fn gradients()
+    {
+        let linear = Linear::<f64>::new(4, 3, true);
+        let mut x = Tensor::<f64>::uniform_with(&[2, 5, 4], -1.0, 1.0, &mut Generator::new(1));
+        x.set_requires_grad(true);
+        let m = Tensor::<f64>::uniform_with(&[2, 5, 3], -1.0, 1.0, &mut Generator::new(2));
+
+        // Checks the gradient of the input through the reshape of 3-D inputs.
+        assert!(gradcheck(|t| linear.forward(&t[0]).mul(&m), std::slice::from_ref(&x), 1e-3, 1e-6));
+
+        let bias = m.data().sum_axis(ndarray::Axis(0)).sum_axis(ndarray::Axis(0));
+        assert!(linear.bias().unwrap().grad().iter().zip(bias.iter()).all(|(g, b)| (g - b).abs() < 1e-12));
+
+        let flat_x = x.data().clone().into_shape((10, 4)).unwrap();
+        let flat_m = m.data().clone().into_shape((10, 3)).unwrap();
+        let weight = flat_x.t().dot(&flat_m);
+        assert!(linear.weight().grad().iter().zip(weight.iter()).all(|(g, w)| (g - w).abs() < 1e-12));
+    }
+}
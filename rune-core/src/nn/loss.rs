@@ -0,0 +1,517 @@
+//
+// MIT License
+// 
+// Copyright (c) 2023 Wilhelm Ågren
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// 
+// File created: 2026-10-17
+// Last updated: 2026-10-17
+//
+
+use crate::datatype::DataType;
+use crate::error::unwrap;
+use crate::error::Result;
+use crate::error::RuneError;
+use crate::tensor::Tensor;
+
+use ndarray::ArrayD;
+use ndarray::Dimension;
+use ndarray::IxDyn;
+
+///
+/// How the per-element losses are combined. `None` returns them as is, in
+/// the shape of the target, while `Mean` and `Sum` return a 0-D scalar that
+/// `backward` can be called on directly.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Reduction
+{
+    None,
+    #[default]
+    Mean,
+    Sum,
+}
+
+///
+/// Mean squared error, (x - y)^2.
+///
+pub fn try_mse_loss<T>(input: &Tensor<T>, target: &Tensor<T>, reduction: Reduction) -> Result<Tensor<T>>
+where T: DataType
+{
+    same_shape("mse_loss", input, target)?;
+    let diff = input.sub(target);
+    Ok(reduce(diff.mul(&diff), reduction))
+}
+
+pub fn mse_loss<T>(input: &Tensor<T>, target: &Tensor<T>, reduction: Reduction) -> Tensor<T>
+where T: DataType
+{
+    unwrap(try_mse_loss(input, target, reduction))
+}
+
+///
+/// Mean absolute error, |x - y|.
+///
+pub fn try_l1_loss<T>(input: &Tensor<T>, target: &Tensor<T>, reduction: Reduction) -> Result<Tensor<T>>
+where T: DataType
+{
+    same_shape("l1_loss", input, target)?;
+    Ok(reduce(input.sub(target).abs(), reduction))
+}
+
+pub fn l1_loss<T>(input: &Tensor<T>, target: &Tensor<T>, reduction: Reduction) -> Tensor<T>
+where T: DataType
+{
+    unwrap(try_l1_loss(input, target, reduction))
+}
+
+///
+/// Squared error for |x - y| < delta and absolute error beyond it, which 
+/// makes it less sensitive to outliers than `mse_loss`.
+///
+///   0.5 * (x - y)^2                  if |x - y| < delta
+///   delta * (|x - y| - 0.5 * delta)  otherwise
+///
+pub fn try_huber_loss<T>(input: &Tensor<T>, target: &Tensor<T>, delta: T, reduction: Reduction)
+    -> Result<Tensor<T>>
+where T: DataType
+{
+    same_shape("huber_loss", input, target)?;
+    let half = T::from(0.5).unwrap();
+    let loss = move |d: T| match d.abs() < delta
+    {
+        true => half * d * d,
+        false => delta * (d.abs() - half * delta),
+    };
+    let d_loss = move |d: T| match d.abs() < delta
+    {
+        true => d,
+        false => delta * d.signum(),
+    };
+    Ok(reduce(input.sub(target).unary(loss, d_loss), reduction))
+}
+
+pub fn huber_loss<T>(input: &Tensor<T>, target: &Tensor<T>, delta: T, reduction: Reduction) -> Tensor<T>
+where T: DataType
+{
+    unwrap(try_huber_loss(input, target, delta, reduction))
+}
+
+///
+/// Binary cross entropy between probabilities `input` and targets in 
+/// [0, 1], -(y * log(x) + (1 - y) * log(1 - x)). The logs are clamped to
+/// be at least -100 so that a probability of exactly 0 or 1 gives a finite
+/// loss and gradient. Prefer `binary_cross_entropy_with_logits` when the
+/// probabilities come from a sigmoid.
+///
+pub fn try_binary_cross_entropy<T>(input: &Tensor<T>, target: &Tensor<T>, reduction: Reduction)
+    -> Result<Tensor<T>>
+where T: DataType
+{
+    same_shape("binary_cross_entropy", input, target)?;
+    let floor = T::from(-100.0).unwrap();
+    let log = |t: &Tensor<T>| t.unary(
+        move |x| x.ln().max(floor),
+        move |x| match x.ln() > floor
+        {
+            true => x.recip(),
+            false => T::zero(),
+        },
+    );
+
+    let one = scalar(T::one());
+    let positive = target.mul(&log(input));
+    let negative = one.sub(target).mul(&log(&one.sub(input)));
+    Ok(reduce(positive.add(&negative).neg(), reduction))
+}
+
+pub fn binary_cross_entropy<T>(input: &Tensor<T>, target: &Tensor<T>, reduction: Reduction) -> Tensor<T>
+where T: DataType
+{
+    unwrap(try_binary_cross_entropy(input, target, reduction))
+}
+
+///
+/// Binary cross entropy on logits, the same as `binary_cross_entropy` of 
+/// sigmoid(x) but computed as softplus(x) - x * y, which is stable for any
+/// magnitude of x.
+///
+pub fn try_binary_cross_entropy_with_logits<T>(input: &Tensor<T>, target: &Tensor<T>, reduction: Reduction)
+    -> Result<Tensor<T>>
+where T: DataType
+{
+    same_shape("binary_cross_entropy_with_logits", input, target)?;
+    Ok(reduce(input.softplus().sub(&input.mul(target)), reduction))
+}
+
+pub fn binary_cross_entropy_with_logits<T>(input: &Tensor<T>, target: &Tensor<T>, reduction: Reduction)
+    -> Tensor<T>
+where T: DataType
+{
+    unwrap(try_binary_cross_entropy_with_logits(input, target, reduction))
+}
+
+///
+/// Negative log likelihood of class indices `target` under log 
+/// probabilities `input`. The input is [N, C] or [N, C, d1, ..] with the 
+/// classes along axis 1, or [C] for a single sample, and the target has 
+/// the input shape without the class axis.
+///
+/// With class `weight`s every loss is scaled by the weight of its target
+/// class and `Mean` divides by the sum of those weights instead of by the
+/// number of targets.
+///
+pub fn try_nll_loss<T>(
+    input: &Tensor<T>,
+    target: &ArrayD<usize>,
+    weight: Option<&ArrayD<T>>,
+    reduction: Reduction,
+) -> Result<Tensor<T>>
+where T: DataType
+{
+    class_loss("nll_loss", input, target, weight, T::zero(), reduction)
+}
+
+pub fn nll_loss<T>(
+    input: &Tensor<T>,
+    target: &ArrayD<usize>,
+    weight: Option<&ArrayD<T>>,
+    reduction: Reduction,
+) -> Tensor<T>
+where T: DataType
+{
+    unwrap(try_nll_loss(input, target, weight, reduction))
+}
+
+///
+/// Cross entropy between logits `input` and class indices `target`, which
+/// is `nll_loss` of the log softmax of the input over the class axis.
+///
+/// With `label_smoothing` = e the target distribution is a mix of the one
+/// hot target and the uniform distribution over all C classes, so that the
+/// target class gets 1 - e + e / C and every other class e / C.
+///
+/// # Example
+///
+/// let logits = Tensor::<f32>::zeros(&[32, 10]);
+/// let target = ArrayD::<usize>::zeros(IxDyn(&[32]));
+/// let loss = cross_entropy(&logits, &target, None, 0.0, Reduction::Mean);
+///
+/// >>> loss.data() = 2.3025851 (= ln 10)
+///
+pub fn try_cross_entropy<T>(
+    input: &Tensor<T>,
+    target: &ArrayD<usize>,
+    weight: Option<&ArrayD<T>>,
+    label_smoothing: T,
+    reduction: Reduction,
+) -> Result<Tensor<T>>
+where T: DataType
+{
+    let axis = class_axis(input);
+    let log_probs = input.try_log_softmax(axis)?;
+    class_loss("cross_entropy", &log_probs, target, weight, label_smoothing, reduction)
+}
+
+pub fn cross_entropy<T>(
+    input: &Tensor<T>,
+    target: &ArrayD<usize>,
+    weight: Option<&ArrayD<T>>,
+    label_smoothing: T,
+    reduction: Reduction,
+) -> Tensor<T>
+where T: DataType
+{
+    unwrap(try_cross_entropy(input, target, weight, label_smoothing, reduction))
+}
+
+///
+/// The loss of every target is a weighted sum of the log probabilities of
+/// its lane along the class axis, -sum_c a_c * log p_c, where 
+/// a_c = (1 - e) * w_y * [c == y] + e / C * w_c. The coefficients are 
+/// constants, so the gradient flows through a product and a sum.
+///
+fn class_loss<T>(
+    op: &'static str,
+    log_probs: &Tensor<T>,
+    target: &ArrayD<usize>,
+    weight: Option<&ArrayD<T>>,
+    label_smoothing: T,
+    reduction: Reduction,
+) -> Result<Tensor<T>>
+where T: DataType
+{
+    let dims = log_probs.shape().dims();
+    let axis = class_axis(log_probs);
+    let classes = dims[axis];
+    let mut expected = dims.clone();
+    expected.remove(axis);
+    if target.shape() != expected.as_slice()
+    {
+        return Err(RuneError::shapes(op, &[dims, target.shape()]));
+    }
+    if let Some(&index) = target.iter().find(|&&y| y >= classes)
+    {
+        return Err(RuneError::IndexOutOfBounds { op, index, size: classes });
+    }
+    let weight = match weight
+    {
+        Some(weight) if weight.shape() != [classes] =>
+            return Err(RuneError::shapes(op, &[dims, weight.shape()])),
+        Some(weight) => weight.clone(),
+        None => ArrayD::ones(IxDyn(&[classes])),
+    };
+
+    let smooth = label_smoothing / T::from(classes).unwrap();
+    let coefficients = ArrayD::from_shape_fn(IxDyn(dims), |pos|
+    {
+        let class = pos[axis];
+        let mut target_pos = pos.slice().to_vec();
+        target_pos.remove(axis);
+        let y = target[IxDyn(&target_pos)];
+        let hot = match class == y
+        {
+            true => (T::one() - label_smoothing) * weight[[y]],
+            false => T::zero(),
+        };
+        -(hot + smooth * weight[[class]])
+    });
+
+    let loss = log_probs.mul(&Tensor::new(coefficients)).sum(&[axis], false);
+    Ok(match reduction
+    {
+        Reduction::Mean =>
+        {
+            let total = target.iter().fold(T::zero(), |total, &y| total + weight[[y]]);
+            loss.sum_all().div(&scalar(total))
+        },
+        _ => reduce(loss, reduction),
+    })
+}
+
+fn class_axis<T: DataType>(input: &Tensor<T>) -> usize
+{
+    match input.shape().ndim()
+    {
+        0 | 1 => 0,
+        _ => 1,
+    }
+}
+
+fn same_shape<T: DataType>(op: &'static str, input: &Tensor<T>, target: &Tensor<T>) -> Result<()>
+{
+    match input.shape() == target.shape()
+    {
+        true => Ok(()),
+        false => Err(RuneError::shapes(op, &[input.shape().dims(), target.shape().dims()])),
+    }
+}
+
+fn reduce<T: DataType>(loss: Tensor<T>, reduction: Reduction) -> Tensor<T>
+{
+    match reduction
+    {
+        Reduction::None => loss,
+        Reduction::Mean => loss.mean_all(),
+        Reduction::Sum => loss.sum_all(),
+    }
+}
+
+fn scalar<T: DataType>(value: T) -> Tensor<T>
+{
+    Tensor::new(ArrayD::from_elem(IxDyn(&[]), value))
+}
+
+///
+/// FUNCTIONAL UNIT TESTING
+///
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::autograd::gradcheck;
+
+    fn tensor(dims: &[usize], values: Vec<f64>) -> Tensor<f64>
+    {
+        let mut t = Tensor::new(ArrayD::from_shape_vec(IxDyn(dims), values).unwrap());
+        t.set_requires_grad(true);
+        t
+    }
+
+    fn close(a: f64, b: f64) -> bool
+    {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn regression()
+    {
+        let x = tensor(&[4], vec![0.0, 1.0, 2.5, -3.0]);
+        let y = tensor(&[4], vec![0.5, 1.0, 0.0, 0.0]);
+
+        assert_eq!(mse_loss(&x, &y, Reduction::Sum).data()[[]], 0.25 + 6.25 + 9.0);
+        assert_eq!(l1_loss(&x, &y, Reduction::Mean).data()[[]], 6.0 / 4.0);
+        assert_eq!(
+            huber_loss(&x, &y, 1.0, Reduction::None).data().as_slice().unwrap(),
+            &[0.125, 0.0, 2.0, 2.5],
+        );
+        assert_eq!(*mse_loss(&x, &y, Reduction::Mean).shape().dims(), Vec::<usize>::new());
+
+        assert!(gradcheck(|t| mse_loss(&t[0], &t[1], Reduction::Mean), &[x.clone(), y.clone()], 1e-6, 1e-6));
+        assert!(gradcheck(|t| l1_loss(&t[0], &t[1], Reduction::Sum), &[x.clone(), y.clone()], 1e-6, 1e-6));
+        assert!(gradcheck(|t| huber_loss(&t[0], &t[1], 1.0, Reduction::Mean), &[x, y], 1e-6, 1e-6));
+    }
+
+    #[test]
+    fn binary()
+    {
+        let p = tensor(&[3], vec![0.9, 0.2, 0.5]);
+        let z = tensor(&[3], vec![2.0, -1.0, 0.0]);
+        let y = tensor(&[3], vec![1.0, 0.0, 1.0]);
+
+        let bce = binary_cross_entropy(&p, &y, Reduction::Sum).data()[[]];
+        assert!(close(bce, -(0.9f64.ln() + 0.8f64.ln() + 0.5f64.ln())));
+
+        let logits = binary_cross_entropy_with_logits(&z, &y, Reduction::None);
+        let probs = binary_cross_entropy(&z.sigmoid(), &y, Reduction::None);
+        for (a, b) in logits.data().iter().zip(probs.data().iter())
+        {
+            assert!(close(*a, *b));
+        }
+
+        assert!(gradcheck(|t| binary_cross_entropy(&t[0], &t[1], Reduction::Mean), &[p, y.clone()], 1e-6, 1e-6));
+        assert!(gradcheck(
+            |t| binary_cross_entropy_with_logits(&t[0], &t[1], Reduction::Mean), &[z, y], 1e-6, 1e-6,
+        ));
+    }
+
+    #[test]
+    fn binary_is_stable()
+    {
+        let z = tensor(&[2], vec![-1000.0, 1000.0]);
+        let y = tensor(&[2], vec![1.0, 0.0]);
+        let p = tensor(&[2], vec![0.0, 1.0]);
+
+        let logits = binary_cross_entropy_with_logits(&z, &y, Reduction::Sum);
+        assert_eq!(logits.data()[[]], 2000.0);
+        logits.backward();
+        assert_eq!(z.grad().as_slice().unwrap(), &[-1.0, 1.0]);
+
+        let probs = binary_cross_entropy(&p, &y, Reduction::Sum);
+        assert_eq!(probs.data()[[]], 200.0);
+        probs.backward();
+        assert!(p.grad().iter().all(|g| g.is_finite()));
+    }
+
+    #[test]
+    fn cross_entropy_values()
+    {
+        let x = tensor(&[2, 3], vec![1.0, 2.0, 3.0, 0.0, 0.0, 1000.0]);
+        let y = ArrayD::from_shape_vec(IxDyn(&[2]), vec![0, 2]).unwrap();
+        let log_z = (1.0 + 1.0f64.exp() + 2.0f64.exp()).ln() + 1.0;
+
+        let loss = cross_entropy(&x, &y, None, 0.0, Reduction::None);
+        assert!(close(loss.data()[[0]], log_z - 1.0));
+        assert_eq!(loss.data()[[1]], 0.0);
+
+        let nll = nll_loss(&x.log_softmax(1), &y, None, Reduction::Mean);
+        assert!(close(nll.data()[[]], (log_z - 1.0) / 2.0));
+
+        // With a uniform prediction smoothing does not change the loss.
+        let target = ArrayD::zeros(IxDyn(&[1]));
+        let uniform = cross_entropy(&Tensor::zeros(&[1, 4]), &target, None, 0.3, Reduction::Sum);
+        assert!(close(uniform.data()[[]], 4.0f64.ln()));
+
+        // Smoothing mixes in the mean of the negative log probabilities.
+        let smoothed = cross_entropy(&x, &y, None, 0.3, Reduction::None);
+        let log_p: Vec<f64> = x.log_softmax(1).data().iter().copied().collect();
+        let expected = -(0.7 * log_p[0] + 0.1 * (log_p[0] + log_p[1] + log_p[2]));
+        assert!(close(smoothed.data()[[0]], expected));
+    }
+
+    #[test]
+    fn cross_entropy_weights()
+    {
+        let x = tensor(&[3, 2], vec![0.0, 1.0, 2.0, 0.5, -1.0, 1.0]);
+        let y = ArrayD::from_shape_vec(IxDyn(&[3]), vec![1, 0, 1]).unwrap();
+        let w = ArrayD::from_shape_vec(IxDyn(&[2]), vec![2.0, 0.5]).unwrap();
+
+        let none = cross_entropy(&x, &y, None, 0.0, Reduction::None);
+        let weighted = cross_entropy(&x, &y, Some(&w), 0.0, Reduction::None);
+        let mean = cross_entropy(&x, &y, Some(&w), 0.0, Reduction::Mean);
+        let per_target = [0.5, 2.0, 0.5];
+
+        for (i, w) in per_target.iter().enumerate()
+        {
+            assert!(close(weighted.data()[[i]], w * none.data()[[i]]));
+        }
+        assert!(close(mean.data()[[]], weighted.data().sum() / 3.0));
+    }
+
+    #[test]
+    fn cross_entropy_gradients()
+    {
+        let values = (0..24).map(|i| ((i * 7) % 11) as f64 / 5.0 - 1.0).collect();
+        let x = [tensor(&[2, 4, 3], values)];
+        let y = ArrayD::from_shape_vec(IxDyn(&[2, 3]), vec![0, 3, 1, 2, 2, 0]).unwrap();
+        let w = ArrayD::from_shape_vec(IxDyn(&[4]), vec![1.0, 0.5, 2.0, 1.5]).unwrap();
+
+        assert!(gradcheck(|t| cross_entropy(&t[0], &y, None, 0.0, Reduction::Mean), &x, 1e-6, 1e-6));
+        assert!(gradcheck(|t| cross_entropy(&t[0], &y, Some(&w), 0.1, Reduction::Mean), &x, 1e-6, 1e-6));
+        assert!(gradcheck(|t| nll_loss(&t[0], &y, Some(&w), Reduction::Sum), &x, 1e-6, 1e-6));
+
+        let single = tensor(&[3], vec![0.0, 1.0, -1.0]);
+        let target = ArrayD::from_elem(IxDyn(&[]), 1);
+        let loss = cross_entropy(&single, &target, None, 0.0, Reduction::None);
+        assert_eq!(*loss.shape().dims(), Vec::<usize>::new());
+        assert!(gradcheck(|t| cross_entropy(&t[0], &target, None, 0.2, Reduction::Sum), &[single], 1e-6, 1e-6));
+    }
+
+    #[test]
+    #[should_panic(expected = "index 3 is out of bounds for size 3")]
+    fn invalid_class()
+    {
+        let target = ArrayD::from_elem(IxDyn(&[2]), 3);
+        let _loss = cross_entropy(&Tensor::<f32>::zeros(&[2, 3]), &target, None, 0.0, Reduction::Mean);
+    }
+
+    #[test]
+    fn invalid()
+    {
+        let x = Tensor::<f32>::zeros(&[2, 3]);
+        let target = ArrayD::from_elem(IxDyn(&[2]), 1);
+
+        assert_eq!(
+            try_mse_loss(&x, &Tensor::zeros(&[3, 2]), Reduction::Mean).err(),
+            Some(RuneError::shapes("mse_loss", &[&[2, 3], &[3, 2]])),
+        );
+        assert!(try_l1_loss(&x, &Tensor::zeros(&[2]), Reduction::Sum).is_err());
+        assert!(try_huber_loss(&x, &Tensor::zeros(&[2, 1]), 1.0, Reduction::None).is_err());
+        assert!(try_binary_cross_entropy(&x, &Tensor::zeros(&[6]), Reduction::Mean).is_err());
+        assert!(try_binary_cross_entropy_with_logits(&x, &Tensor::zeros(&[1, 3]), Reduction::Mean).is_err());
+        assert!(try_nll_loss(&x, &ArrayD::from_elem(IxDyn(&[3]), 1), None, Reduction::Mean).is_err());
+        assert!(try_nll_loss(&x, &target, Some(&ArrayD::ones(IxDyn(&[2]))), Reduction::Mean).is_err());
+        assert_eq!(
+            try_cross_entropy(&x, &ArrayD::from_elem(IxDyn(&[2]), 3), None, 0.0, Reduction::Mean).err(),
+            Some(RuneError::IndexOutOfBounds { op: "cross_entropy", index: 3, size: 3 }),
+        );
+        assert!(try_cross_entropy(&x, &target, None, 0.1, Reduction::Mean).is_ok());
+    }
+}
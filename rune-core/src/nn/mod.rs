@@ -22,10 +22,17 @@
 // SOFTWARE.
 // 
 // File created: 2023-03-12
-// Last updated: 2023-03-12
+// Last updated: 2026-10-17
 //
 
+pub mod conv;
+pub mod dropout;
+pub mod init;
+pub mod linear;
+pub mod loss;
 pub mod module;
+pub mod norm;
 pub mod parameter;
+pub mod pool;
 pub mod sequential;
 
@@ -1,36 +1,287 @@
+//
+// MIT License
+// 
+// Copyright (c) 2023 Wilhelm Ågren
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// 
+// File created: 2023-03-12
+// Last updated: 2026-10-17
+//
+
 use crate::datatype::DataType;
-use crate::nn::parameter::Parameter;
+use crate::error::Result;
+use crate::error::RuneError;
 use crate::tensor::Tensor;
 
-use ndarray_rand::rand_distr::Distribution;
-use ndarray_rand::rand_distr::Uniform;
+use ndarray::ArrayD;
+
+use std::collections::BTreeMap;
+
+///
+/// A snapshot of the parameters and buffers of a module by name, which is
+/// what gets saved to and restored from a checkpoint.
+///
+pub type StateDict<T> = BTreeMap<String, ArrayD<T>>;
 
-pub struct Module<'a, T: DataType>
+///
+/// The building block of every model. A module owns its parameters, and
+/// possibly other modules, and maps an input tensor to an output tensor in
+/// `forward`. Parameters are handed out as tensor handles, so the tensors 
+/// returned by `parameters` share their data and gradients with the module
+/// and can be given to an optimizer directly.
+///
+/// Names of parameters are dot separated paths, where containers prefix the
+/// names of their children, e.g. `0.weight` for the weight of the first 
+/// layer of a `Sequential`.
+///
+/// Buffers are tensors of state that is not trained, e.g. the running
+/// statistics of batch norm. They are left out of `parameters`, so that
+/// optimizers never see them, but are part of the `state_dict`.
+///
+/// A module is in training mode when created. Every module keeps its own
+/// mode in `set_training` and `is_training`, also those that behave the same
+/// during evaluation, and containers forward the mode to all of their
+/// children.
+///
+/// # Example
+///
+/// let model = Linear::<f32>::new(3, 2, true);
+/// let y = model.forward(&Tensor::ones(&[4, 3]));
+/// y.sum_all().backward();
+///
+/// >>> model.named_parameters() = [("weight", [3, 2]), ("bias", [2])]
+/// >>> model.parameters()[0].grad() = [[4.0, 4.0], [4.0, 4.0], [4.0, 4.0]]
+///
+pub trait Module<T: DataType>
 {
-    parameters: Vec<Tensor<'a, T>>
+    fn forward(&self, input: &Tensor<T>) -> Tensor<T>;
+
+    fn named_parameters(&self) -> Vec<(String, Tensor<T>)>;
+
+    fn parameters_mut(&mut self) -> Vec<&mut Tensor<T>>;
+
+    fn parameters(&self) -> Vec<Tensor<T>>
+    {
+        self.named_parameters().into_iter().map(|(_, p)| p).collect()
+    }
+
+    fn named_buffers(&self) -> Vec<(String, Tensor<T>)>
+    {
+        Vec::new()
+    }
+
+    ///
+    /// Copy the data of every parameter and buffer into a `StateDict`.
+    ///
+    fn state_dict(&self) -> StateDict<T>
+    {
+        self.named_parameters()
+            .into_iter()
+            .chain(self.named_buffers())
+            .map(|(name, t)| (name, t.data().clone()))
+            .collect()
+    }
+
+    ///
+    /// Overwrite every parameter and buffer with its entry in `state`.
+    /// Nothing is written unless all entries are present and have the
+    /// right shape, while entries that the module does not have are ignored.
+    ///
+    fn load_state_dict(&self, state: &StateDict<T>) -> Result<()>
+    {
+        let tensors: Vec<(String, Tensor<T>)> = self.named_parameters()
+            .into_iter()
+            .chain(self.named_buffers())
+            .collect();
+        for (name, tensor) in tensors.iter()
+        {
+            match state.get(name)
+            {
+                None => return Err(RuneError::MissingKey { op: "load_state_dict", key: name.clone() }),
+                Some(value) if value.shape() != tensor.shape().dims().as_slice() => return Err(
+                    RuneError::shapes("load_state_dict", &[tensor.shape().dims(), value.shape()]),
+                ),
+                Some(_) => (),
+            }
+        }
+        for (name, tensor) in tensors
+        {
+            tensor.data_mut().assign(&state[&name]);
+        }
+        Ok(())
+    }
+
+    fn zero_grad(&self)
+    {
+        for parameter in self.parameters()
+        {
+            parameter.zero_grad();
+        }
+    }
+
+    fn set_training(&mut self, training: bool);
+
+    fn is_training(&self) -> bool;
+
+    fn train(&mut self)
+    {
+        self.set_training(true);
+    }
+
+    fn eval(&mut self)
+    {
+        self.set_training(false);
+    }
 }
 
-impl<'a, T: DataType> Module<'a, T>
+///
+/// FUNCTIONAL UNIT TESTING
+///
+#[cfg(test)]
+mod tests
 {
-    pub fn affine(fan_in: usize, fan_out: usize) -> Self
-    where Uniform<f32>: Distribution<T>
+    use super::*;
+    use crate::nn::parameter::Parameter;
+
+    use ndarray::ArrayD;
+    use ndarray::IxDyn;
+
+    struct Affine
+    {
+        weight: Tensor<f64>,
+        bias: Tensor<f64>,
+        training: bool,
+    }
+
+    impl Affine
     {
-        let mut weights = Parameter::uniform::<T>(&[fan_in, fan_out], -1.0, 1.0);
-        let mut bias = Parameter::uniform(&[fan_out, 1], -1.0, 1.0);
-        Module
+        fn new() -> Self
         {
-            parameters: vec![weights, bias],
+            Affine
+            {
+                weight: Parameter::new(ArrayD::from_elem(IxDyn(&[3, 2]), 2.0)),
+                bias: Parameter::new(ArrayD::from_elem(IxDyn(&[2]), 1.0)),
+                training: true,
+            }
         }
     }
 
-    /*
-    pub fn forward(&'a self, t: &'a Tensor<'a, T>) -> Tensor<'a, T>
+    impl Module<f64> for Affine
     {
-        Tensor::zeros(&[784, 1]).add(&self.parameters[1])
+        fn forward(&self, input: &Tensor<f64>) -> Tensor<f64>
+        {
+            input.matmul(&self.weight).add(&self.bias)
+        }
+
+        fn named_parameters(&self) -> Vec<(String, Tensor<f64>)>
+        {
+            vec![
+                ("weight".to_string(), self.weight.clone()),
+                ("bias".to_string(), self.bias.clone()),
+            ]
+        }
 
-        // let r = t.dot(&self.parameters[0]);
-        // r = r.add(&self.parameters[1]);
+        fn parameters_mut(&mut self) -> Vec<&mut Tensor<f64>>
+        {
+            vec![&mut self.weight, &mut self.bias]
+        }
+
+        fn set_training(&mut self, training: bool)
+        {
+            self.training = training;
+        }
+
+        fn is_training(&self) -> bool
+        {
+            self.training
+        }
     }
-    */
-}
 
+    #[test]
+    fn parameters()
+    {
+        let mut affine = Affine::new();
+        let names: Vec<String> = affine.named_parameters().into_iter().map(|(n, _)| n).collect();
+
+        assert_eq!(names, vec!["weight", "bias"]);
+        assert_eq!(affine.parameters().len(), 2);
+
+        for parameter in affine.parameters_mut()
+        {
+            parameter.set_requires_grad(false);
+        }
+        assert!(affine.parameters().iter().all(|p| !p.requires_grad()));
+    }
+
+    #[test]
+    fn shared_gradients()
+    {
+        let affine = Affine::new();
+        let y = affine.forward(&Tensor::ones(&[4, 3]));
+
+        assert_eq!(y.data().as_slice().unwrap(), &[7.0; 8]);
+
+        y.sum_all().backward();
+        let parameters = affine.parameters();
+        assert_eq!(parameters[0].grad().as_slice().unwrap(), &[4.0; 6]);
+        assert_eq!(parameters[1].grad().as_slice().unwrap(), &[4.0; 2]);
+
+        affine.zero_grad();
+        assert!(affine.weight.grad().iter().all(|&g| g == 0.0));
+        assert!(affine.bias.grad().iter().all(|&g| g == 0.0));
+    }
+
+    #[test]
+    fn state_dict()
+    {
+        let affine = Affine::new();
+        let mut state = affine.state_dict();
+        assert_eq!(state.keys().collect::<Vec<_>>(), vec!["bias", "weight"]);
+
+        state.get_mut("bias").unwrap().fill(-1.0);
+        state.insert("unused".to_string(), ArrayD::zeros(IxDyn(&[1])));
+        affine.load_state_dict(&state).unwrap();
+        assert_eq!(affine.bias.data().as_slice().unwrap(), &[-1.0; 2]);
+
+        state.insert("weight".to_string(), ArrayD::zeros(IxDyn(&[2, 3])));
+        state.get_mut("bias").unwrap().fill(5.0);
+        assert!(affine.load_state_dict(&state).is_err());
+        assert_eq!(affine.bias.data().as_slice().unwrap(), &[-1.0; 2]);
+
+        state.remove("weight");
+        assert_eq!(
+            affine.load_state_dict(&state),
+            Err(RuneError::MissingKey { op: "load_state_dict", key: "weight".to_string() }),
+        );
+    }
+
+    #[test]
+    fn modes()
+    {
+        let mut affine = Affine::new();
+        assert!(affine.is_training());
+
+        affine.eval();
+        assert!(!affine.is_training());
+
+        affine.train();
+        assert!(affine.is_training());
+    }
+}
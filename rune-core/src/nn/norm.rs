@@ -0,0 +1,708 @@
+//
+// MIT License
+// 
+// Copyright (c) 2023 Wilhelm Ågren
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// 
+// File created: 2026-10-17
+// Last updated: 2026-10-17
+//
+
+use crate::datatype::DataType;
+use crate::error::unwrap;
+use crate::error::Result;
+use crate::error::RuneError;
+use crate::nn::module::Module;
+use crate::nn::parameter::Parameter;
+use crate::tensor::Tensor;
+
+use ndarray::ArrayD;
+use ndarray::IxDyn;
+
+///
+/// Batch normalization over the channels of inputs of [batch, channels] or
+/// [batch, channels, length] for `BatchNorm1d` and of [batch, channels,
+/// height, width] for `BatchNorm2d`. Every channel is normalized with its
+/// mean and variance over all other axes, and then scaled and shifted by a
+/// learned weight and bias when `affine`.
+///
+/// In training mode the statistics of the batch are used, and the running
+/// mean and variance buffers are updated as r = (1 - momentum) * r +
+/// momentum * stat, with the unbiased variance. In eval mode the running
+/// statistics are used instead, so outputs no longer depend on the batch.
+///
+/// # Example
+///
+/// let mut bn = BatchNorm2d::<f32>::new(16, true);
+/// let y = bn.forward(&Tensor::normal(&[8, 16, 32, 32], 2.0, 1.0));
+/// bn.eval();
+///
+/// >>> bn.named_buffers() = [("running_mean", [16]), ("running_var", [16])]
+///
+pub struct BatchNorm<T: DataType, const N: usize>
+{
+    weight: Option<Tensor<T>>,
+    bias: Option<Tensor<T>>,
+    running_mean: Tensor<T>,
+    running_var: Tensor<T>,
+    momentum: T,
+    eps: T,
+    training: bool,
+}
+
+pub type BatchNorm1d<T> = BatchNorm<T, 1>;
+pub type BatchNorm2d<T> = BatchNorm<T, 2>;
+
+impl<T: DataType, const N: usize> BatchNorm<T, N>
+{
+    pub fn new(num_features: usize, affine: bool) -> Self
+    {
+        let (weight, bias) = affine_parameters(&[num_features], affine);
+        BatchNorm {
+            weight,
+            bias,
+            running_mean: Tensor::zeros(&[num_features]),
+            running_var: Tensor::ones(&[num_features]),
+            momentum: T::from(0.1).unwrap(),
+            eps: T::from(1e-5).unwrap(),
+            training: true,
+        }
+    }
+
+    pub fn momentum(mut self, momentum: T) -> Self
+    {
+        self.momentum = momentum;
+        self
+    }
+
+    pub fn eps(mut self, eps: T) -> Self
+    {
+        self.eps = eps;
+        self
+    }
+
+    pub fn weight(&self) -> Option<&Tensor<T>>
+    {
+        self.weight.as_ref()
+    }
+
+    pub fn bias(&self) -> Option<&Tensor<T>>
+    {
+        self.bias.as_ref()
+    }
+
+    pub fn running_mean(&self) -> &Tensor<T>
+    {
+        &self.running_mean
+    }
+
+    pub fn running_var(&self) -> &Tensor<T>
+    {
+        &self.running_var
+    }
+
+    pub fn num_features(&self) -> usize
+    {
+        self.running_mean.shape().dims()[0]
+    }
+}
+
+impl<T: DataType, const N: usize> Module<T> for BatchNorm<T, N>
+{
+    fn forward(&self, input: &Tensor<T>) -> Tensor<T>
+    {
+        let dims = input.shape().dims();
+        unwrap(check_batch_norm::<N>(dims, self.num_features(), self.training));
+        let shape = channel_shape(self.num_features(), dims.len() - 2);
+
+        let output = match self.training
+        {
+            true =>
+            {
+                let axes: Vec<usize> = (0..dims.len()).filter(|&a| a != 1).collect();
+                let count: usize = axes.iter().map(|&a| dims[a]).product();
+                let (output, mean, var) = unwrap(input.normalize_with_stats("batch_norm", &axes, self.eps));
+                let keep = T::one() - self.momentum;
+                let unbiased = T::from(count).unwrap() / T::from(count - 1).unwrap();
+                let channels = IxDyn(&[self.num_features()]);
+                self.running_mean.data_mut().zip_mut_with(&mean.into_shape(channels.clone()).unwrap(), |r, &m|
+                {
+                    *r = keep * *r + self.momentum * m;
+                });
+                self.running_var.data_mut().zip_mut_with(&var.into_shape(channels).unwrap(), |r, &v|
+                {
+                    *r = keep * *r + self.momentum * v * unbiased;
+                });
+                output
+            },
+            false =>
+            {
+                let mean = self.running_mean.data().clone().into_shape(IxDyn(&shape)).unwrap();
+                let scale = self.running_var.data().mapv(|v| T::one() / (v + self.eps).sqrt());
+                let scale = scale.into_shape(IxDyn(&shape)).unwrap();
+                input.sub(&Tensor::new(mean)).mul(&Tensor::new(scale))
+            },
+        };
+        affine(output, &self.weight, &self.bias, &shape)
+    }
+
+    fn named_parameters(&self) -> Vec<(String, Tensor<T>)>
+    {
+        named_affine(&self.weight, &self.bias)
+    }
+
+    fn parameters_mut(&mut self) -> Vec<&mut Tensor<T>>
+    {
+        self.weight.iter_mut().chain(self.bias.iter_mut()).collect()
+    }
+
+    fn named_buffers(&self) -> Vec<(String, Tensor<T>)>
+    {
+        vec![
+            ("running_mean".to_string(), self.running_mean.clone()),
+            ("running_var".to_string(), self.running_var.clone()),
+        ]
+    }
+
+    fn set_training(&mut self, training: bool)
+    {
+        self.training = training;
+    }
+
+    fn is_training(&self) -> bool
+    {
+        self.training
+    }
+}
+
+///
+/// Layer normalization over the trailing dims given by `normalized_shape`,
+/// e.g. the features of every token of [batch, tokens, features]. The
+/// affine weight and bias have the normalized shape, so every feature is
+/// scaled separately.
+///
+/// # Example
+///
+/// let ln = LayerNorm::<f32>::new(&[512], true);
+///
+/// >>> ln.forward(&Tensor::ones(&[8, 128, 512])).shape() = Shape { dims: [8, 128, 512] }
+///
+pub struct LayerNorm<T: DataType>
+{
+    normalized_shape: Vec<usize>,
+    weight: Option<Tensor<T>>,
+    bias: Option<Tensor<T>>,
+    eps: T,
+    training: bool,
+}
+
+impl<T: DataType> LayerNorm<T>
+{
+    pub fn new(normalized_shape: &[usize], affine: bool) -> Self
+    {
+        let (weight, bias) = affine_parameters(normalized_shape, affine);
+        LayerNorm
+        {
+            normalized_shape: normalized_shape.to_vec(),
+            weight,
+            bias,
+            eps: T::from(1e-5).unwrap(),
+            training: true,
+        }
+    }
+
+    pub fn eps(mut self, eps: T) -> Self
+    {
+        self.eps = eps;
+        self
+    }
+
+    pub fn weight(&self) -> Option<&Tensor<T>>
+    {
+        self.weight.as_ref()
+    }
+
+    pub fn bias(&self) -> Option<&Tensor<T>>
+    {
+        self.bias.as_ref()
+    }
+}
+
+impl<T: DataType> Module<T> for LayerNorm<T>
+{
+    fn forward(&self, input: &Tensor<T>) -> Tensor<T>
+    {
+        let dims = input.shape().dims();
+        unwrap(check_layer_norm(dims, &self.normalized_shape));
+        let k = self.normalized_shape.len();
+        let axes: Vec<usize> = (dims.len() - k..dims.len()).collect();
+        let output = unwrap(input.normalize_with_stats("layer_norm", &axes, self.eps)).0;
+        affine(output, &self.weight, &self.bias, &self.normalized_shape)
+    }
+
+    fn named_parameters(&self) -> Vec<(String, Tensor<T>)>
+    {
+        named_affine(&self.weight, &self.bias)
+    }
+
+    fn parameters_mut(&mut self) -> Vec<&mut Tensor<T>>
+    {
+        self.weight.iter_mut().chain(self.bias.iter_mut()).collect()
+    }
+
+    fn set_training(&mut self, training: bool)
+    {
+        self.training = training;
+    }
+
+    fn is_training(&self) -> bool
+    {
+        self.training
+    }
+}
+
+///
+/// Group normalization of inputs of [batch, channels, *spatial], where the
+/// channels are split into `groups` groups that are normalized over their
+/// channels and all spatial positions. One group is layer norm over all
+/// but the batch dim, and one group per channel is instance norm. The
+/// affine weight and bias are per channel.
+///
+/// # Example
+///
+/// let gn = GroupNorm::<f32>::new(8, 64, true);
+///
+/// >>> gn.forward(&Tensor::ones(&[4, 64, 16, 16])).shape() = Shape { dims: [4, 64, 16, 16] }
+///
+pub struct GroupNorm<T: DataType>
+{
+    groups: usize,
+    weight: Option<Tensor<T>>,
+    bias: Option<Tensor<T>>,
+    channels: usize,
+    eps: T,
+    training: bool,
+}
+
+impl<T: DataType> GroupNorm<T>
+{
+    pub fn new(groups: usize, channels: usize, affine: bool) -> Self
+    {
+        assert!(
+            groups > 0 && channels.is_multiple_of(groups),
+            "Channels {} are not divisible by {} groups", channels, groups,
+        );
+
+        let (weight, bias) = affine_parameters(&[channels], affine);
+        GroupNorm { groups, weight, bias, channels, eps: T::from(1e-5).unwrap(), training: true }
+    }
+
+    pub fn eps(mut self, eps: T) -> Self
+    {
+        self.eps = eps;
+        self
+    }
+
+    pub fn weight(&self) -> Option<&Tensor<T>>
+    {
+        self.weight.as_ref()
+    }
+
+    pub fn bias(&self) -> Option<&Tensor<T>>
+    {
+        self.bias.as_ref()
+    }
+}
+
+impl<T: DataType> Module<T> for GroupNorm<T>
+{
+    fn forward(&self, input: &Tensor<T>) -> Tensor<T>
+    {
+        let dims = input.shape().dims();
+        unwrap(check_group_norm(dims, self.channels));
+        let grouped = input.reshape(&[dims[0] as isize, self.groups as isize, -1]);
+        let output = unwrap(grouped.normalize_with_stats("group_norm", &[2], self.eps)).0;
+        let output = output.reshape(&dims.iter().map(|&d| d as isize).collect::<Vec<isize>>());
+        affine(output, &self.weight, &self.bias, &channel_shape(self.channels, dims.len() - 2))
+    }
+
+    fn named_parameters(&self) -> Vec<(String, Tensor<T>)>
+    {
+        named_affine(&self.weight, &self.bias)
+    }
+
+    fn parameters_mut(&mut self) -> Vec<&mut Tensor<T>>
+    {
+        self.weight.iter_mut().chain(self.bias.iter_mut()).collect()
+    }
+
+    fn set_training(&mut self, training: bool)
+    {
+        self.training = training;
+    }
+
+    fn is_training(&self) -> bool
+    {
+        self.training
+    }
+}
+
+///
+/// Instance normalization of inputs of [batch, channels, *spatial], or
+/// without the batch dim, where every channel of every sample is normalized
+/// over its N spatial dims. Statistics are never tracked, so it behaves the
+/// same in training and eval mode.
+///
+pub struct InstanceNorm<T: DataType, const N: usize>
+{
+    weight: Option<Tensor<T>>,
+    bias: Option<Tensor<T>>,
+    num_features: usize,
+    eps: T,
+    training: bool,
+}
+
+pub type InstanceNorm1d<T> = InstanceNorm<T, 1>;
+pub type InstanceNorm2d<T> = InstanceNorm<T, 2>;
+
+impl<T: DataType, const N: usize> InstanceNorm<T, N>
+{
+    pub fn new(num_features: usize, affine: bool) -> Self
+    {
+        let (weight, bias) = affine_parameters(&[num_features], affine);
+        InstanceNorm { weight, bias, num_features, eps: T::from(1e-5).unwrap(), training: true }
+    }
+
+    pub fn eps(mut self, eps: T) -> Self
+    {
+        self.eps = eps;
+        self
+    }
+
+    pub fn weight(&self) -> Option<&Tensor<T>>
+    {
+        self.weight.as_ref()
+    }
+
+    pub fn bias(&self) -> Option<&Tensor<T>>
+    {
+        self.bias.as_ref()
+    }
+}
+
+impl<T: DataType, const N: usize> Module<T> for InstanceNorm<T, N>
+{
+    fn forward(&self, input: &Tensor<T>) -> Tensor<T>
+    {
+        let dims = input.shape().dims();
+        unwrap(check_instance_norm::<N>(dims, self.num_features));
+        let axes: Vec<usize> = (dims.len() - N..dims.len()).collect();
+        let output = unwrap(input.normalize_with_stats("instance_norm", &axes, self.eps)).0;
+        affine(output, &self.weight, &self.bias, &channel_shape(self.num_features, N))
+    }
+
+    fn named_parameters(&self) -> Vec<(String, Tensor<T>)>
+    {
+        named_affine(&self.weight, &self.bias)
+    }
+
+    fn parameters_mut(&mut self) -> Vec<&mut Tensor<T>>
+    {
+        self.weight.iter_mut().chain(self.bias.iter_mut()).collect()
+    }
+
+    fn set_training(&mut self, training: bool)
+    {
+        self.training = training;
+    }
+
+    fn is_training(&self) -> bool
+    {
+        self.training
+    }
+}
+
+///
+/// Inputs of [batch, features, *spatial] with N spatial dims, where
+/// `BatchNorm1d` also takes [batch, features]. Training needs more than one
+/// value per feature to estimate a variance.
+///
+fn check_batch_norm<const N: usize>(dims: &[usize], features: usize, training: bool) -> Result<()>
+{
+    if (dims.len() != N + 2 && !(N == 1 && dims.len() == 2)) || dims[1] != features
+    {
+        return Err(RuneError::shapes("batch_norm", &[dims, &[features]]));
+    }
+    if training && dims[0] * dims[2..].iter().product::<usize>() < 2
+    {
+        let reason = "expected more than 1 value per channel in training".to_string();
+        return Err(RuneError::invalid("batch_norm", dims, reason));
+    }
+    Ok(())
+}
+
+fn check_layer_norm(dims: &[usize], normalized_shape: &[usize]) -> Result<()>
+{
+    let k = normalized_shape.len();
+    match dims.len() >= k && dims[dims.len() - k..] == *normalized_shape
+    {
+        true => Ok(()),
+        false => Err(RuneError::shapes("layer_norm", &[dims, normalized_shape])),
+    }
+}
+
+fn check_group_norm(dims: &[usize], channels: usize) -> Result<()>
+{
+    match dims.len() >= 2 && dims[1] == channels
+    {
+        true => Ok(()),
+        false => Err(RuneError::shapes("group_norm", &[dims, &[channels]])),
+    }
+}
+
+fn check_instance_norm<const N: usize>(dims: &[usize], features: usize) -> Result<()>
+{
+    match (dims.len() == N + 1 || dims.len() == N + 2) && dims[dims.len() - N - 1] == features
+    {
+        true => Ok(()),
+        false => Err(RuneError::shapes("instance_norm", &[dims, &[features]])),
+    }
+}
+
+///
+/// A weight of ones and a bias of zeros, so that the affine transform
+/// starts out as the identity.
+///
+fn affine_parameters<T: DataType>(dims: &[usize], affine: bool) -> (Option<Tensor<T>>, Option<Tensor<T>>)
+{
+    match affine
+    {
+        true => (
+            Some(Parameter::new(ArrayD::ones(IxDyn(dims)))),
+            Some(Parameter::new(ArrayD::zeros(IxDyn(dims)))),
+        ),
+        false => (None, None),
+    }
+}
+
+///
+/// [channels, 1, .., 1] with a 1 for every trailing dim, which broadcasts
+/// over the channel axis of both batched and unbatched inputs.
+///
+fn channel_shape(channels: usize, trailing: usize) -> Vec<usize>
+{
+    let mut shape = vec![channels];
+    shape.extend(vec![1; trailing]);
+    shape
+}
+
+fn affine<T: DataType>(output: Tensor<T>, weight: &Option<Tensor<T>>, bias: &Option<Tensor<T>>, shape: &[usize]) -> Tensor<T>
+{
+    let shape: Vec<isize> = shape.iter().map(|&d| d as isize).collect();
+    let output = match weight
+    {
+        Some(weight) => output.mul(&weight.reshape(&shape)),
+        None => output,
+    };
+    match bias
+    {
+        Some(bias) => output.add(&bias.reshape(&shape)),
+        None => output,
+    }
+}
+
+fn named_affine<T: DataType>(weight: &Option<Tensor<T>>, bias: &Option<Tensor<T>>) -> Vec<(String, Tensor<T>)>
+{
+    let weight = weight.iter().map(|w| ("weight".to_string(), w.clone()));
+    let bias = bias.iter().map(|b| ("bias".to_string(), b.clone()));
+    weight.chain(bias).collect()
+}
+
+///
+/// FUNCTIONAL UNIT TESTING
+///
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::autograd::gradcheck;
+    use crate::nn::sequential::Sequential;
+    use crate::random::Generator;
+
+    fn random(dims: &[usize], seed: u64) -> Tensor<f64>
+    {
+        let mut t = Tensor::uniform_with(dims, -1.0, 1.0, &mut Generator::new(seed));
+        t.set_requires_grad(true);
+        t
+    }
+
+    fn close(a: &ArrayD<f64>, b: &ArrayD<f64>) -> bool
+    {
+        a.shape() == b.shape() && a.iter().zip(b.iter()).all(|(x, y)| (x - y).abs() < 1e-10)
+    }
+
+    #[test]
+    fn batch_norm_training()
+    {
+        let bn = BatchNorm1d::<f64>::new(2, true).momentum(0.5);
+        let x = Tensor::new(ArrayD::from_shape_vec(IxDyn(&[3, 2]), vec![1.0, 10.0, 2.0, 20.0, 3.0, 30.0]).unwrap());
+        let y = bn.forward(&x);
+
+        assert!((y.data()[[0, 0]] + 1.0 / (2.0 / 3.0 + 1e-5f64).sqrt()).abs() < 1e-10);
+        assert!(y.data()[[1, 1]].abs() < 1e-10);
+
+        // mean [2, 20], unbiased var [1, 100]
+        assert_eq!(bn.running_mean().data().as_slice().unwrap(), &[1.0, 10.0]);
+        assert_eq!(bn.running_var().data().as_slice().unwrap(), &[1.0, 50.5]);
+    }
+
+    #[test]
+    fn batch_norm_eval()
+    {
+        let mut bn = BatchNorm2d::<f64>::new(3, true);
+        for seed in 0..50
+        {
+            bn.forward(&Tensor::normal_with(&[8, 3, 4, 4], 2.0, 3.0, &mut Generator::new(seed)));
+        }
+        assert!(bn.running_mean().data().iter().all(|m| (m - 2.0).abs() < 0.2));
+        assert!(bn.running_var().data().iter().all(|v| (v - 9.0).abs() < 1.0));
+
+        bn.eval();
+        let before = bn.running_mean().data().clone();
+        let x = random(&[2, 3, 2, 2], 1);
+        let y = bn.forward(&x);
+        assert_eq!(*bn.running_mean().data(), before);
+
+        let mean = before.into_shape(IxDyn(&[3, 1, 1])).unwrap();
+        let std = bn.running_var().data().mapv(|v| (v + 1e-5).sqrt()).into_shape(IxDyn(&[3, 1, 1])).unwrap();
+        assert!(close(&y.data(), &((&*x.data() - &mean) / &std)));
+    }
+
+    #[test]
+    fn buffers_and_state()
+    {
+        let mut model = Sequential::<f64>::new();
+        model.push(LayerNorm::new(&[3], true));
+        model.push(BatchNorm1d::new(3, true));
+        model.push(BatchNorm1d::new(3, false));
+
+        let names: Vec<String> = model.named_parameters().into_iter().map(|(n, _)| n).collect();
+        let buffers: Vec<String> = model.named_buffers().into_iter().map(|(n, _)| n).collect();
+        assert_eq!(names, vec!["0.weight", "0.bias", "1.weight", "1.bias"]);
+        assert_eq!(buffers, vec!["1.running_mean", "1.running_var", "2.running_mean", "2.running_var"]);
+        assert_eq!(model.state_dict().len(), 8);
+
+        model.forward(&random(&[4, 3], 2));
+        let state = model.state_dict();
+        let fresh = Sequential::<f64>::from(vec![
+            Box::new(LayerNorm::new(&[3], true)) as Box<dyn Module<f64>>,
+            Box::new(BatchNorm1d::new(3, true)),
+            Box::new(BatchNorm1d::new(3, false)),
+        ]);
+        fresh.load_state_dict(&state).unwrap();
+        assert_eq!(fresh.state_dict(), state);
+        assert!(fresh.named_buffers()[0].1.data().iter().all(|&m| m != 0.0));
+    }
+
+    #[test]
+    fn layer_norm()
+    {
+        let ln = LayerNorm::<f64>::new(&[3, 4], true);
+        let x = random(&[2, 5, 3, 4], 3);
+        let y = ln.forward(&x);
+
+        assert!(close(&y.data(), &x.normalize(&[2, 3], 1e-5).data()));
+        assert_eq!(*ln.weight().unwrap().shape().dims(), vec![3, 4]);
+    }
+
+    #[test]
+    fn group_norm()
+    {
+        let x = random(&[2, 6, 3, 3], 4);
+
+        let gn = GroupNorm::<f64>::new(1, 6, false);
+        assert!(close(&gn.forward(&x).data(), &x.normalize(&[1, 2, 3], 1e-5).data()));
+
+        let gn = GroupNorm::<f64>::new(6, 6, false);
+        let inn = InstanceNorm2d::<f64>::new(6, false);
+        assert!(close(&gn.forward(&x).data(), &inn.forward(&x).data()));
+
+        let gn = GroupNorm::<f64>::new(3, 6, true);
+        let y = gn.forward(&x);
+        let first = x.data().slice(ndarray::s![0, 0..2, .., ..]).to_owned();
+        let mean = first.mean().unwrap();
+        let std = (first.mapv(|v| (v - mean).powi(2)).mean().unwrap() + 1e-5).sqrt();
+        assert!((y.data()[[0, 1, 2, 0]] - (x.data()[[0, 1, 2, 0]] - mean) / std).abs() < 1e-10);
+    }
+
+    #[test]
+    fn instance_norm()
+    {
+        let inn = InstanceNorm1d::<f64>::new(3, true);
+        let x = random(&[3, 7], 5);
+        assert!(close(&inn.forward(&x).data(), &x.normalize(&[1], 1e-5).data()));
+        assert!(inn.named_buffers().is_empty());
+        assert_eq!(inn.parameters().len(), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn wrong_channels()
+    {
+        BatchNorm2d::<f32>::new(4, true).forward(&Tensor::ones(&[2, 3, 4, 4]));
+    }
+
+    #[test]
+    fn checks()
+    {
+        assert!(check_batch_norm::<1>(&[4, 3], 3, true).is_ok());
+        assert!(check_batch_norm::<1>(&[1, 3], 3, true).is_err());
+        assert!(check_batch_norm::<1>(&[1, 3], 3, false).is_ok());
+        assert!(check_batch_norm::<2>(&[4, 3, 5], 3, true).is_err());
+        assert!(check_layer_norm(&[2, 3, 4], &[3, 4]).is_ok());
+        assert!(check_layer_norm(&[4], &[3, 4]).is_err());
+        assert!(check_group_norm(&[2, 6, 3], 6).is_ok());
+        assert!(check_group_norm(&[6], 6).is_err());
+        assert!(check_instance_norm::<2>(&[3, 4, 4], 3).is_ok());
+        assert!(check_instance_norm::<2>(&[2, 3, 4], 3).is_err());
+    }
+
+    #[test]
+    fn gradients()
+    {
+        let m = Tensor::new(random(&[3, 4, 2], 6).data().clone());
+        let inputs = [random(&[3, 4, 2], 7), random(&[4], 8), random(&[4], 9)];
+        let f = |t: &[Tensor<f64>]|
+        {
+            let mut bn = BatchNorm1d::<f64>::new(4, true);
+            *bn.parameters_mut()[0] = t[1].clone();
+            *bn.parameters_mut()[1] = t[2].clone();
+            bn.forward(&t[0]).mul(&m)
+        };
+        assert!(gradcheck(f, &inputs, 1e-6, 1e-6));
+
+        let gn = GroupNorm::<f64>::new(2, 4, true);
+        gn.parameters()[0].data_mut().assign(&random(&[4], 10).data());
+        assert!(gradcheck(|t| gn.forward(&t[0]).mul(&m), &[random(&[3, 4, 2], 11)], 1e-6, 1e-6));
+
+        let ln = LayerNorm::<f64>::new(&[2], true);
+        assert!(gradcheck(|t| ln.forward(&t[0]).mul(&m), &[random(&[3, 4, 2], 12)], 1e-6, 1e-6));
+    }
+}
@@ -22,24 +22,21 @@
 // SOFTWARE.
 // 
 // File created: 2023-03-12
-// Last updated: 2023-03-12
+// Last updated: 2026-10-17
 //
 
 use crate::datatype::DataType;
+use crate::random::Generator;
 use crate::tensor::Tensor;
 
-
 use ndarray::ArrayD;
 
-use ndarray_rand::rand_distr::Distribution;
-use ndarray_rand::rand_distr::Normal;
-use ndarray_rand::rand_distr::Uniform;
-
 pub struct Parameter {}
 
 impl Parameter
 {
-    pub fn new<'a, T>(data: ArrayD<T>) -> Tensor<'a, T>
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new<T>(data: ArrayD<T>) -> Tensor<T>
     where T: DataType
     {
         let mut parameter = Tensor::new(data);
@@ -47,20 +44,35 @@ impl Parameter
         parameter
     }
 
-    pub fn uniform<'a, T>(dims: &[usize], low: f32, high: f32) -> Tensor<'a, T>
-    where T: DataType, Uniform<f32>: Distribution<T>
+    pub fn uniform<T>(dims: &[usize], low: f32, high: f32) -> Tensor<T>
+    where T: DataType
     {
         let mut parameter = Tensor::<T>::uniform(dims, low, high);
         parameter.set_requires_grad(true);
         parameter
     }
 
-    pub fn normal<'a, T>(dims: &[usize], mu: f32, sigma: f32) -> Tensor<'a, T>
-    where T: DataType, Normal<f32>: Distribution<T>
+    pub fn uniform_with<T>(dims: &[usize], low: f32, high: f32, generator: &mut Generator) -> Tensor<T>
+    where T: DataType
+    {
+        let mut parameter = Tensor::<T>::uniform_with(dims, low, high, generator);
+        parameter.set_requires_grad(true);
+        parameter
+    }
+
+    pub fn normal<T>(dims: &[usize], mu: f32, sigma: f32) -> Tensor<T>
+    where T: DataType
     {
         let mut parameter = Tensor::<T>::normal(dims, mu, sigma);
         parameter.set_requires_grad(true);
         parameter
     }
-}
 
+    pub fn normal_with<T>(dims: &[usize], mu: f32, sigma: f32, generator: &mut Generator) -> Tensor<T>
+    where T: DataType
+    {
+        let mut parameter = Tensor::<T>::normal_with(dims, mu, sigma, generator);
+        parameter.set_requires_grad(true);
+        parameter
+    }
+}
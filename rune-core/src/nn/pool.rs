@@ -0,0 +1,353 @@
+//
+// MIT License
+// 
+// Copyright (c) 2023 Wilhelm Ågren
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// 
+// File created: 2026-10-17
+// Last updated: 2026-10-17
+//
+
+use crate::datatype::DataType;
+use crate::error::unwrap;
+use crate::nn::module::Module;
+use crate::tensor::Tensor;
+
+use ndarray::ArrayD;
+
+///
+/// Max pooling over N spatial dims, see `Tensor::max_pool2d`. The stride
+/// defaults to the kernel, so that windows do not overlap, and padding and
+/// `ceil_mode` default to 0 and false.
+///
+/// # Example
+///
+/// let pool = MaxPool2d::new([2, 2]);
+/// let y = Module::<f32>::forward(&pool, &Tensor::ones(&[8, 16, 32, 32]));
+///
+/// >>> y.shape() = Shape { dims: [8, 16, 16, 16] }
+///
+pub struct MaxPool<const N: usize>
+{
+    kernel: [usize; N],
+    stride: [usize; N],
+    padding: [usize; N],
+    ceil_mode: bool,
+    training: bool,
+}
+
+pub type MaxPool1d = MaxPool<1>;
+pub type MaxPool2d = MaxPool<2>;
+
+impl<const N: usize> MaxPool<N>
+{
+    pub fn new(kernel: [usize; N]) -> Self
+    {
+        MaxPool { kernel, stride: kernel, padding: [0; N], ceil_mode: false, training: true }
+    }
+
+    pub fn stride(mut self, stride: [usize; N]) -> Self
+    {
+        self.stride = stride;
+        self
+    }
+
+    pub fn padding(mut self, padding: [usize; N]) -> Self
+    {
+        self.padding = padding;
+        self
+    }
+
+    pub fn ceil_mode(mut self, ceil_mode: bool) -> Self
+    {
+        self.ceil_mode = ceil_mode;
+        self
+    }
+
+    ///
+    /// Pool and also return the indices of the maxes, for `max_unpool2d`.
+    ///
+    pub fn forward_with_indices<T: DataType>(&self, input: &Tensor<T>) -> (Tensor<T>, ArrayD<usize>)
+    {
+        unwrap(input.try_max_pool_with_indices(self.kernel, self.stride, self.padding, self.ceil_mode))
+    }
+}
+
+impl<T: DataType, const N: usize> Module<T> for MaxPool<N>
+{
+    fn forward(&self, input: &Tensor<T>) -> Tensor<T>
+    {
+        self.forward_with_indices(input).0
+    }
+
+    fn named_parameters(&self) -> Vec<(String, Tensor<T>)>
+    {
+        Vec::new()
+    }
+
+    fn parameters_mut(&mut self) -> Vec<&mut Tensor<T>>
+    {
+        Vec::new()
+    }
+
+    fn set_training(&mut self, training: bool)
+    {
+        self.training = training;
+    }
+
+    fn is_training(&self) -> bool
+    {
+        self.training
+    }
+}
+
+///
+/// Average pooling over N spatial dims, see `Tensor::avg_pool2d`, with the
+/// same defaults as `MaxPool`.
+///
+pub struct AvgPool<const N: usize>
+{
+    kernel: [usize; N],
+    stride: [usize; N],
+    padding: [usize; N],
+    ceil_mode: bool,
+    training: bool,
+}
+
+pub type AvgPool1d = AvgPool<1>;
+pub type AvgPool2d = AvgPool<2>;
+
+impl<const N: usize> AvgPool<N>
+{
+    pub fn new(kernel: [usize; N]) -> Self
+    {
+        AvgPool { kernel, stride: kernel, padding: [0; N], ceil_mode: false, training: true }
+    }
+
+    pub fn stride(mut self, stride: [usize; N]) -> Self
+    {
+        self.stride = stride;
+        self
+    }
+
+    pub fn padding(mut self, padding: [usize; N]) -> Self
+    {
+        self.padding = padding;
+        self
+    }
+
+    pub fn ceil_mode(mut self, ceil_mode: bool) -> Self
+    {
+        self.ceil_mode = ceil_mode;
+        self
+    }
+}
+
+impl<T: DataType, const N: usize> Module<T> for AvgPool<N>
+{
+    fn forward(&self, input: &Tensor<T>) -> Tensor<T>
+    {
+        unwrap(input.try_avg_pool(self.kernel, self.stride, self.padding, self.ceil_mode))
+    }
+
+    fn named_parameters(&self) -> Vec<(String, Tensor<T>)>
+    {
+        Vec::new()
+    }
+
+    fn parameters_mut(&mut self) -> Vec<&mut Tensor<T>>
+    {
+        Vec::new()
+    }
+
+    fn set_training(&mut self, training: bool)
+    {
+        self.training = training;
+    }
+
+    fn is_training(&self) -> bool
+    {
+        self.training
+    }
+}
+
+///
+/// Average pooling to a fixed output size whatever the input size, e.g.
+/// global average pooling with an output of ones.
+///
+/// # Example
+///
+/// let pool = AdaptiveAvgPool2d::new([1, 1]);
+/// let y = Module::<f32>::forward(&pool, &Tensor::ones(&[8, 512, 7, 7]));
+///
+/// >>> y.shape() = Shape { dims: [8, 512, 1, 1] }
+///
+pub struct AdaptiveAvgPool<const N: usize>
+{
+    output: [usize; N],
+    training: bool,
+}
+
+pub type AdaptiveAvgPool1d = AdaptiveAvgPool<1>;
+pub type AdaptiveAvgPool2d = AdaptiveAvgPool<2>;
+
+impl<const N: usize> AdaptiveAvgPool<N>
+{
+    pub fn new(output: [usize; N]) -> Self
+    {
+        AdaptiveAvgPool { output, training: true }
+    }
+}
+
+impl<T: DataType, const N: usize> Module<T> for AdaptiveAvgPool<N>
+{
+    fn forward(&self, input: &Tensor<T>) -> Tensor<T>
+    {
+        unwrap(input.try_adaptive_avg_pool(self.output))
+    }
+
+    fn named_parameters(&self) -> Vec<(String, Tensor<T>)>
+    {
+        Vec::new()
+    }
+
+    fn parameters_mut(&mut self) -> Vec<&mut Tensor<T>>
+    {
+        Vec::new()
+    }
+
+    fn set_training(&mut self, training: bool)
+    {
+        self.training = training;
+    }
+
+    fn is_training(&self) -> bool
+    {
+        self.training
+    }
+}
+
+///
+/// Max pooling to a fixed output size whatever the input size.
+///
+pub struct AdaptiveMaxPool<const N: usize>
+{
+    output: [usize; N],
+    training: bool,
+}
+
+pub type AdaptiveMaxPool1d = AdaptiveMaxPool<1>;
+pub type AdaptiveMaxPool2d = AdaptiveMaxPool<2>;
+
+impl<const N: usize> AdaptiveMaxPool<N>
+{
+    pub fn new(output: [usize; N]) -> Self
+    {
+        AdaptiveMaxPool { output, training: true }
+    }
+}
+
+impl<T: DataType, const N: usize> Module<T> for AdaptiveMaxPool<N>
+{
+    fn forward(&self, input: &Tensor<T>) -> Tensor<T>
+    {
+        unwrap(input.try_adaptive_max_pool(self.output))
+    }
+
+    fn named_parameters(&self) -> Vec<(String, Tensor<T>)>
+    {
+        Vec::new()
+    }
+
+    fn parameters_mut(&mut self) -> Vec<&mut Tensor<T>>
+    {
+        Vec::new()
+    }
+
+    fn set_training(&mut self, training: bool)
+    {
+        self.training = training;
+    }
+
+    fn is_training(&self) -> bool
+    {
+        self.training
+    }
+}
+
+///
+/// FUNCTIONAL UNIT TESTING
+///
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::nn::conv::Conv2d;
+    use crate::nn::sequential::Sequential;
+    use crate::random::Generator;
+
+    #[test]
+    fn shapes()
+    {
+        let x = Tensor::<f32>::ones(&[2, 3, 9, 9]);
+
+        assert_eq!(*MaxPool2d::new([2, 2]).forward(&x).shape().dims(), vec![2, 3, 4, 4]);
+        assert_eq!(*MaxPool2d::new([2, 2]).ceil_mode(true).forward(&x).shape().dims(), vec![2, 3, 5, 5]);
+        assert_eq!(*AvgPool2d::new([3, 3]).stride([2, 2]).padding([1, 1]).forward(&x).shape().dims(), vec![2, 3, 5, 5]);
+        assert_eq!(*AdaptiveMaxPool2d::new([2, 3]).forward(&x).shape().dims(), vec![2, 3, 2, 3]);
+
+        let x = Tensor::<f32>::ones(&[3, 10]);
+        assert_eq!(*MaxPool1d::new([3]).stride([1]).forward(&x).shape().dims(), vec![3, 8]);
+        assert_eq!(*AvgPool1d::new([2]).forward(&x).shape().dims(), vec![3, 5]);
+        assert_eq!(*AdaptiveAvgPool1d::new([1]).forward(&x).shape().dims(), vec![3, 1]);
+        assert_eq!(*AdaptiveMaxPool1d::new([4]).forward(&x).shape().dims(), vec![3, 4]);
+    }
+
+    #[test]
+    fn indices()
+    {
+        let x = Tensor::<f64>::uniform_with(&[1, 2, 4, 4], 1.0, 2.0, &mut Generator::new(0));
+        let pool = MaxPool2d::new([2, 2]);
+        let (y, indices) = pool.forward_with_indices(&x);
+
+        assert_eq!(*y.data(), *pool.forward(&x).data());
+        // Unpooling fills with zeros, so the round trip only holds for non-negative maxima.
+        assert_eq!(y.max_unpool2d(&indices, [4, 4]).max_pool2d([2, 2], [2, 2], [0, 0], false).data().clone(), *y.data());
+    }
+
+    #[test]
+    fn in_sequential()
+    {
+        let mut model = Sequential::<f64>::new();
+        model.push(Conv2d::new(1, 4, [3, 3], 1, true).padding([1, 1]));
+        model.push(MaxPool2d::new([2, 2]));
+        model.push(AdaptiveAvgPool2d::new([1, 1]));
+
+        let y = model.forward(&Tensor::ones(&[2, 1, 8, 8]));
+        assert_eq!(*y.shape().dims(), vec![2, 4, 1, 1]);
+        assert_eq!(model.parameters().len(), 2);
+
+        y.sum_all().backward();
+        assert!(model.parameters()[0].grad().iter().any(|&g| g != 0.0));
+
+        model.eval();
+        assert!(model.iter().all(|m| !m.is_training()));
+    }
+}
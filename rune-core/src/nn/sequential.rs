@@ -0,0 +1,287 @@
+//
+// MIT License
+// 
+// Copyright (c) 2023 Wilhelm Ågren
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// 
+// File created: 2026-10-17
+// Last updated: 2026-10-17
+//
+
+use crate::datatype::DataType;
+use crate::nn::module::Module;
+use crate::tensor::Tensor;
+
+use std::ops::Index;
+use std::ops::IndexMut;
+use std::slice::Iter;
+use std::slice::IterMut;
+
+///
+/// A container that chains the forwards of its modules, the output of one
+/// module is the input of the next. The parameters of module i are named
+/// with the prefix `i.`, e.g. `0.weight` and `1.bias`.
+///
+/// # Example
+///
+/// let mut model = Sequential::<f32>::new();
+/// model.push(Linear::new(784, 128, true));
+/// model.push(Linear::new(128, 10, true));
+/// let y = model.forward(&Tensor::ones(&[32, 784]));
+///
+/// >>> y.shape() = Shape { dims: [32, 10] }
+/// >>> model.named_parameters() = ["0.weight", "0.bias", "1.weight", "1.bias"]
+///
+pub struct Sequential<T: DataType>
+{
+    modules: Vec<Box<dyn Module<T>>>,
+    training: bool,
+}
+
+impl<T: DataType> Default for Sequential<T>
+{
+    fn default() -> Self
+    {
+        Sequential::new()
+    }
+}
+
+impl<T: DataType> From<Vec<Box<dyn Module<T>>>> for Sequential<T>
+{
+    fn from(modules: Vec<Box<dyn Module<T>>>) -> Self
+    {
+        Sequential { modules, training: true }
+    }
+}
+
+impl<T: DataType> Sequential<T>
+{
+    pub fn new() -> Self
+    {
+        Sequential::from(Vec::new())
+    }
+
+    ///
+    /// Append a module, it is put in the same mode as the container.
+    ///
+    pub fn push<M>(&mut self, module: M)
+    where M: Module<T> + 'static
+    {
+        self.insert(self.modules.len(), module);
+    }
+
+    ///
+    /// Insert a module at position `index`, which shifts the names of the
+    /// parameters of all modules after it.
+    ///
+    pub fn insert<M>(&mut self, index: usize, mut module: M)
+    where M: Module<T> + 'static
+    {
+        module.set_training(self.training);
+        self.modules.insert(index, Box::new(module));
+    }
+
+    pub fn len(&self) -> usize
+    {
+        self.modules.len()
+    }
+
+    pub fn is_empty(&self) -> bool
+    {
+        self.modules.is_empty()
+    }
+
+    pub fn iter(&self) -> Iter<'_, Box<dyn Module<T>>>
+    {
+        self.modules.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, Box<dyn Module<T>>>
+    {
+        self.modules.iter_mut()
+    }
+}
+
+impl<T: DataType> Module<T> for Sequential<T>
+{
+    fn forward(&self, input: &Tensor<T>) -> Tensor<T>
+    {
+        self.modules.iter().fold(input.clone(), |x, module| module.forward(&x))
+    }
+
+    fn named_parameters(&self) -> Vec<(String, Tensor<T>)>
+    {
+        self.modules
+            .iter()
+            .enumerate()
+            .flat_map(|(i, module)|
+            {
+                module.named_parameters()
+                    .into_iter()
+                    .map(move |(name, p)| (format!("{}.{}", i, name), p))
+            })
+            .collect()
+    }
+
+    fn parameters_mut(&mut self) -> Vec<&mut Tensor<T>>
+    {
+        self.modules.iter_mut().flat_map(|module| module.parameters_mut()).collect()
+    }
+
+    fn named_buffers(&self) -> Vec<(String, Tensor<T>)>
+    {
+        self.modules
+            .iter()
+            .enumerate()
+            .flat_map(|(i, module)|
+            {
+                module.named_buffers()
+                    .into_iter()
+                    .map(move |(name, b)| (format!("{}.{}", i, name), b))
+            })
+            .collect()
+    }
+
+    fn set_training(&mut self, training: bool)
+    {
+        self.training = training;
+        for module in self.modules.iter_mut()
+        {
+            module.set_training(training);
+        }
+    }
+
+    fn is_training(&self) -> bool
+    {
+        self.training
+    }
+}
+
+impl<T: DataType> Index<usize> for Sequential<T>
+{
+    type Output = dyn Module<T>;
+
+    fn index(&self, index: usize) -> &Self::Output
+    {
+        self.modules[index].as_ref()
+    }
+}
+
+impl<T: DataType> IndexMut<usize> for Sequential<T>
+{
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output
+    {
+        self.modules[index].as_mut()
+    }
+}
+
+impl<'a, T: DataType> IntoIterator for &'a Sequential<T>
+{
+    type Item = &'a Box<dyn Module<T>>;
+    type IntoIter = Iter<'a, Box<dyn Module<T>>>;
+
+    fn into_iter(self) -> Self::IntoIter
+    {
+        self.modules.iter()
+    }
+}
+
+impl<'a, T: DataType> IntoIterator for &'a mut Sequential<T>
+{
+    type Item = &'a mut Box<dyn Module<T>>;
+    type IntoIter = IterMut<'a, Box<dyn Module<T>>>;
+
+    fn into_iter(self) -> Self::IntoIter
+    {
+        self.modules.iter_mut()
+    }
+}
+
+///
+/// FUNCTIONAL UNIT TESTING
+///
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::nn::linear::Linear;
+
+    fn mlp() -> Sequential<f32>
+    {
+        let mut model = Sequential::new();
+        model.push(Linear::new(8, 16, true));
+        model.push(Linear::new(16, 4, false));
+        model.insert(1, Linear::new(16, 16, true));
+        model
+    }
+
+    #[test]
+    fn forward()
+    {
+        let model = mlp();
+        let x = Tensor::ones(&[32, 8]);
+        let y = model.forward(&x);
+        let expected = model[2].forward(&model[1].forward(&model[0].forward(&x)));
+
+        assert_eq!(model.len(), 3);
+        assert_eq!(*y.shape().dims(), vec![32, 4]);
+        assert_eq!(*y.data(), *expected.data());
+        assert_eq!(*Sequential::new().forward(&x).data(), *x.data());
+    }
+
+    #[test]
+    fn parameters()
+    {
+        let mut model = mlp();
+        let names: Vec<String> = model.named_parameters().into_iter().map(|(n, _)| n).collect();
+
+        assert_eq!(names, vec!["0.weight", "0.bias", "1.weight", "1.bias", "2.weight"]);
+        assert_eq!(*model.parameters()[2].shape().dims(), vec![16, 16]);
+        assert_eq!(model.parameters_mut().len(), 5);
+        assert_eq!(model.iter().map(|m| m.parameters().len()).sum::<usize>(), 5);
+
+        model.forward(&Tensor::ones(&[2, 8])).sum_all().backward();
+        assert!(model[0].parameters()[0].grad().iter().any(|&g| g != 0.0));
+        model.zero_grad();
+        assert!(model.parameters().iter().all(|p| p.grad().iter().all(|&g| g == 0.0)));
+    }
+
+    #[test]
+    fn modes()
+    {
+        let mut inner = Sequential::<f32>::new();
+        inner.push(Linear::new(2, 2, true));
+        let mut model = Sequential::new();
+        model.push(inner);
+
+        model.eval();
+        assert!(model.iter().all(|m| !m.is_training()));
+
+        model.push(Sequential::<f32>::new());
+        assert!(!model[1].is_training());
+
+        for module in &mut model
+        {
+            module.train();
+        }
+        assert!(model[0].is_training() && model[1].is_training());
+        assert_eq!(model.named_parameters()[0].0, "0.0.weight");
+    }
+}
@@ -0,0 +1,902 @@
+//
+// MIT License
+// 
+// Copyright (c) 2023 Wilhelm Ågren
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// 
+// File created: 2026-10-17
+// Last updated: 2026-10-17
+//
+
+use crate::datatype::DataType;
+use crate::error::unwrap;
+use crate::error::Result;
+use crate::error::RuneError;
+use crate::ops::gemm::gemm;
+use crate::ops::gemm::matmul_2d;
+use crate::tensor::Tensor;
+use crate::utils::contiguous;
+
+use ndarray::indices;
+use ndarray::ArrayD;
+use ndarray::ArrayView2;
+use ndarray::IxDyn;
+
+const CONV_OPS: [&str; 3] = ["conv1d", "conv2d", "conv3d"];
+const CONV_TRANSPOSE_OPS: [&str; 3] = ["conv_transpose1d", "conv_transpose2d", "conv_transpose3d"];
+
+///
+/// The positions that a sliding window reads from a single channel of an
+/// image. For every position in the kernel and every output position, in
+/// that order, `offsets` holds the flat offset into the channel, or None
+/// where the window hangs over the zero padding.
+///
+/// This turns convolutions into matrix products, im2col copies the windows
+/// of an image into the columns of a [channels * kernel, positions] matrix
+/// and col2im scatters such a matrix back, summing overlapping windows.
+///
+pub(crate) struct Window
+{
+    pub(crate) kernel: usize,
+    pub(crate) positions: usize,
+    pub(crate) image: usize,
+    offsets: Vec<Option<usize>>,
+}
+
+impl Window
+{
+    pub(crate) fn new(
+        input: &[usize],
+        kernel: &[usize],
+        output: &[usize],
+        stride: &[usize],
+        padding: &[usize],
+        dilation: &[usize],
+    ) -> Self
+    {
+        let mut offsets = Vec::new();
+        for k in indices(IxDyn(kernel))
+        {
+            for o in indices(IxDyn(output))
+            {
+                let mut offset = Some(0);
+                for (d, &size) in input.iter().enumerate()
+                {
+                    let pos = (o[d] * stride[d] + k[d] * dilation[d]) as isize - padding[d] as isize;
+                    offset = match (offset, pos >= 0 && pos < size as isize)
+                    {
+                        (Some(offset), true) => Some(offset * size + pos as usize),
+                        _ => None,
+                    };
+                }
+                offsets.push(offset);
+            }
+        }
+
+        Window {
+            kernel: kernel.iter().product(),
+            positions: output.iter().product(),
+            image: input.iter().product(),
+            offsets,
+        }
+    }
+
+    pub(crate) fn im2col<T: DataType>(&self, image: &[T], channels: usize) -> Vec<T>
+    {
+        let mut cols = Vec::with_capacity(channels * self.offsets.len());
+        for channel in image.chunks(self.image).take(channels)
+        {
+            cols.extend(self.offsets.iter().map(|o| o.map_or(T::zero(), |o| channel[o])));
+        }
+        cols
+    }
+
+    pub(crate) fn col2im<T: DataType>(&self, cols: &[T], channels: usize, image: &mut [T])
+    {
+        let rows = cols.chunks(self.offsets.len());
+        for (channel, col) in image.chunks_mut(self.image).take(channels).zip(rows)
+        {
+            for (&o, &v) in self.offsets.iter().zip(col.iter())
+            {
+                if let Some(o) = o
+                {
+                    channel[o] += v;
+                }
+            }
+        }
+    }
+}
+
+///
+/// The spatial output dims of sliding a dilated kernel over a zero padded
+/// input, floor((input + 2 * padding - dilation * (kernel - 1) - 1) / stride) + 1.
+///
+pub(crate) fn conv_output(
+    op: &'static str,
+    input: &[usize],
+    kernel: &[usize],
+    stride: &[usize],
+    padding: &[usize],
+    dilation: &[usize],
+) -> Result<Vec<usize>>
+{
+    if stride.contains(&0) || dilation.contains(&0) || kernel.contains(&0)
+    {
+        let reason = "stride, dilation and kernel must be positive".to_string();
+        return Err(RuneError::invalid(op, input, reason));
+    }
+
+    let mut output = Vec::with_capacity(input.len());
+    for (d, &size) in input.iter().enumerate()
+    {
+        let span = dilation[d] * (kernel[d] - 1) + 1;
+        let padded = size + 2 * padding[d];
+        if span > padded
+        {
+            let reason = format!("kernel of {} with dilation {} does not fit in the padded input", kernel[d], dilation[d]);
+            return Err(RuneError::invalid(op, input, reason));
+        }
+        output.push((padded - span) / stride[d] + 1);
+    }
+    Ok(output)
+}
+
+///
+/// The spatial output dims of a transposed convolution, which are
+/// (input - 1) * stride - 2 * padding + dilation * (kernel - 1) +
+/// output_padding + 1, where the output padding must be smaller than either
+/// the stride or the dilation.
+///
+pub(crate) fn conv_transpose_output(
+    op: &'static str,
+    input: &[usize],
+    kernel: &[usize],
+    stride: &[usize],
+    padding: &[usize],
+    output_padding: &[usize],
+    dilation: &[usize],
+) -> Result<Vec<usize>>
+{
+    if stride.contains(&0) || dilation.contains(&0) || kernel.contains(&0)
+    {
+        let reason = "stride, dilation and kernel must be positive".to_string();
+        return Err(RuneError::invalid(op, input, reason));
+    }
+
+    let mut output = Vec::with_capacity(input.len());
+    for (d, &size) in input.iter().enumerate()
+    {
+        if output_padding[d] >= stride[d].max(dilation[d])
+        {
+            let reason = format!("output padding {} must be smaller than the stride or the dilation", output_padding[d]);
+            return Err(RuneError::invalid(op, input, reason));
+        }
+        if size == 0
+        {
+            return Err(RuneError::invalid(op, input, "spatial dims must be positive".to_string()));
+        }
+
+        let full = (size - 1) * stride[d] + dilation[d] * (kernel[d] - 1) + output_padding[d] + 1;
+        if full <= 2 * padding[d]
+        {
+            let reason = format!("padding {} leaves no output", padding[d]);
+            return Err(RuneError::invalid(op, input, reason));
+        }
+        output.push(full - 2 * padding[d]);
+    }
+    Ok(output)
+}
+
+///
+/// Convolutions over inputs of [batch, in_channels, *spatial], or without
+/// the batch dim, with weights of [out_channels, in_channels / groups,
+/// *kernel] and an optional bias of [out_channels]. The channels are split
+/// into `groups` groups that are convolved separately, and the output is
+/// [batch, out_channels, *output] where every output dim is
+/// floor((input + 2 * padding - dilation * (kernel - 1) - 1) / stride) + 1.
+///
+/// Every batch entry and group is lowered to one matrix product between
+/// the weight of the group and the im2col matrix of its input channels.
+///
+impl<T: DataType> Tensor<T>
+{
+    ///
+    /// Convolve a sequence of [batch, channels, length].
+    ///
+    /// # Example
+    ///
+    /// let x = Tensor::<f32>::ones(&[8, 3, 100]);
+    /// let w = Tensor::<f32>::ones(&[16, 3, 5]);
+    ///
+    /// >>> x.conv1d(&w, None, [2], [2], [1], 1).shape() = Shape { dims: [8, 16, 50] }
+    ///
+    pub fn try_conv1d(
+        &self,
+        weight: &Tensor<T>,
+        bias: Option<&Tensor<T>>,
+        stride: [usize; 1],
+        padding: [usize; 1],
+        dilation: [usize; 1],
+        groups: usize,
+    ) -> Result<Tensor<T>>
+    {
+        self.try_conv(weight, bias, stride, padding, dilation, groups)
+    }
+
+    pub fn conv1d(
+        &self,
+        weight: &Tensor<T>,
+        bias: Option<&Tensor<T>>,
+        stride: [usize; 1],
+        padding: [usize; 1],
+        dilation: [usize; 1],
+        groups: usize,
+    ) -> Tensor<T>
+    {
+        unwrap(self.try_conv1d(weight, bias, stride, padding, dilation, groups))
+    }
+
+    ///
+    /// Convolve an image of [batch, channels, height, width].
+    ///
+    /// # Example
+    ///
+    /// let x = Tensor::<f32>::ones(&[8, 3, 32, 32]);
+    /// let w = Tensor::<f32>::ones(&[16, 3, 3, 3]);
+    ///
+    /// >>> x.conv2d(&w, None, [1, 1], [1, 1], [1, 1], 1).shape() = Shape { dims: [8, 16, 32, 32] }
+    ///
+    pub fn try_conv2d(
+        &self,
+        weight: &Tensor<T>,
+        bias: Option<&Tensor<T>>,
+        stride: [usize; 2],
+        padding: [usize; 2],
+        dilation: [usize; 2],
+        groups: usize,
+    ) -> Result<Tensor<T>>
+    {
+        self.try_conv(weight, bias, stride, padding, dilation, groups)
+    }
+
+    pub fn conv2d(
+        &self,
+        weight: &Tensor<T>,
+        bias: Option<&Tensor<T>>,
+        stride: [usize; 2],
+        padding: [usize; 2],
+        dilation: [usize; 2],
+        groups: usize,
+    ) -> Tensor<T>
+    {
+        unwrap(self.try_conv2d(weight, bias, stride, padding, dilation, groups))
+    }
+
+    ///
+    /// Convolve a volume of [batch, channels, depth, height, width].
+    ///
+    pub fn try_conv3d(
+        &self,
+        weight: &Tensor<T>,
+        bias: Option<&Tensor<T>>,
+        stride: [usize; 3],
+        padding: [usize; 3],
+        dilation: [usize; 3],
+        groups: usize,
+    ) -> Result<Tensor<T>>
+    {
+        self.try_conv(weight, bias, stride, padding, dilation, groups)
+    }
+
+    pub fn conv3d(
+        &self,
+        weight: &Tensor<T>,
+        bias: Option<&Tensor<T>>,
+        stride: [usize; 3],
+        padding: [usize; 3],
+        dilation: [usize; 3],
+        groups: usize,
+    ) -> Tensor<T>
+    {
+        unwrap(self.try_conv3d(weight, bias, stride, padding, dilation, groups))
+    }
+
+    pub(crate) fn try_conv<const N: usize>(
+        &self,
+        weight: &Tensor<T>,
+        bias: Option<&Tensor<T>>,
+        stride: [usize; N],
+        padding: [usize; N],
+        dilation: [usize; N],
+        groups: usize,
+    ) -> Result<Tensor<T>>
+    {
+        let op = CONV_OPS[N - 1];
+        let dims = self.shape().dims();
+        let w_dims = weight.shape().dims();
+        if dims.len() == N + 1
+        {
+            return self.try_unsqueeze(0)?
+                .try_conv(weight, bias, stride, padding, dilation, groups)?
+                .try_squeeze(0);
+        }
+        if dims.len() != N + 2
+        {
+            let reason = format!("expected an input of {} or {} dims", N + 1, N + 2);
+            return Err(RuneError::invalid(op, dims, reason));
+        }
+        if w_dims.len() != N + 2
+        {
+            let reason = format!("expected a weight of {} dims", N + 2);
+            return Err(RuneError::invalid(op, w_dims, reason));
+        }
+        if groups == 0 || !w_dims[0].is_multiple_of(groups)
+        {
+            let reason = format!("out channels must be divisible by {} groups", groups);
+            return Err(RuneError::invalid(op, w_dims, reason));
+        }
+        if w_dims[1] * groups != dims[1]
+        {
+            return Err(RuneError::shapes(op, &[dims, w_dims]));
+        }
+        if let Some(bias) = bias
+        {
+            if *bias.shape().dims() != [w_dims[0]]
+            {
+                return Err(RuneError::shapes(op, &[w_dims, bias.shape().dims()]));
+            }
+        }
+
+        let (batch, in_channels, out_channels) = (dims[0], dims[1], w_dims[0]);
+        let (group_in, group_out) = (in_channels / groups, out_channels / groups);
+        let output = conv_output(op, &dims[2..], &w_dims[2..], &stride, &padding, &dilation)?;
+        let window = Window::new(&dims[2..], &w_dims[2..], &output, &stride, &padding, &dilation);
+        let (k, l) = (group_in * window.kernel, window.positions);
+
+        let x = self.data().clone();
+        let w = weight.data().clone();
+        let mut y = vec![T::zero(); batch * out_channels * l];
+        {
+            let (xs, ws) = (contiguous(op, &x)?, contiguous(op, &w)?);
+            for n in 0..batch
+            {
+                for g in 0..groups
+                {
+                    let image = &xs[(n * in_channels + g * group_in) * window.image..];
+                    let cols = window.im2col(image, group_in);
+                    let y_g = &mut y[(n * out_channels + g * group_out) * l..][..group_out * l];
+                    gemm(group_out, k, l, &ws[g * group_out * k..][..group_out * k], &cols, y_g);
+                }
+            }
+        }
+        if let Some(bias) = bias
+        {
+            let bias = bias.data();
+            for (channel, y_c) in y.chunks_mut(l).enumerate()
+            {
+                let b = bias[[channel % out_channels]];
+                y_c.iter_mut().for_each(|v| *v += b);
+            }
+        }
+
+        let mut out_dims = vec![batch, out_channels];
+        out_dims.extend(output);
+        let data = ArrayD::from_shape_vec(IxDyn(&out_dims), y).unwrap();
+
+        let mut parents = vec![self.clone(), weight.clone()];
+        parents.extend(bias.cloned());
+        let has_bias = bias.is_some();
+        Ok(Tensor::from_op(data, parents, ||
+        {
+            // dW_g = dY_g @ cols^T, dcols = W_g^T @ dY_g and dx = col2im(dcols).
+            Box::new(move |grad|
+            {
+                let grad = grad.as_standard_layout();
+                let gs = grad.as_slice().unwrap();
+                let (xs, ws) = (x.as_slice().unwrap(), w.as_slice().unwrap());
+                let mut dx = vec![T::zero(); xs.len()];
+                let mut dw = vec![T::zero(); ws.len()];
+                for n in 0..batch
+                {
+                    for g in 0..groups
+                    {
+                        let offset = (n * in_channels + g * group_in) * window.image;
+                        let cols = window.im2col(&xs[offset..], group_in);
+                        let cols = ArrayView2::from_shape((k, l), &cols).unwrap();
+                        let w_g = ArrayView2::from_shape((group_out, k), &ws[g * group_out * k..][..group_out * k]).unwrap();
+                        let g_y = ArrayView2::from_shape((group_out, l), &gs[(n * out_channels + g * group_out) * l..][..group_out * l]).unwrap();
+
+                        let dw_g = matmul_2d(&g_y, &cols.t());
+                        dw[g * group_out * k..][..group_out * k].iter_mut()
+                            .zip(dw_g.iter())
+                            .for_each(|(a, &b)| *a += b);
+
+                        let dcols = matmul_2d(&w_g.t(), &g_y);
+                        window.col2im(dcols.as_slice().unwrap(), group_in, &mut dx[offset..]);
+                    }
+                }
+
+                let mut grads = vec![
+                    ArrayD::from_shape_vec(x.raw_dim(), dx).unwrap(),
+                    ArrayD::from_shape_vec(w.raw_dim(), dw).unwrap(),
+                ];
+                if has_bias
+                {
+                    let mut db = vec![T::zero(); out_channels];
+                    for (channel, g_c) in gs.chunks(l).enumerate()
+                    {
+                        db[channel % out_channels] += g_c.iter().fold(T::zero(), |s, &v| s + v);
+                    }
+                    grads.push(ArrayD::from_shape_vec(IxDyn(&[out_channels]), db).unwrap());
+                }
+                grads
+            })
+        }))
+    }
+}
+
+///
+/// Transposed convolutions, the gradient of a convolution with respect to
+/// its input, which upsample by the stride. The input is [batch, in_channels,
+/// *spatial], or without the batch dim, the weight is [in_channels,
+/// out_channels / groups, *kernel] and the optional bias [out_channels]. The
+/// output dims are given by `conv_transpose_output`, where `output_padding`
+/// picks between the input sizes that a strided convolution maps to the
+/// same output size.
+///
+/// Every batch entry and group is one matrix product between the transposed
+/// weight of the group and its input channels, which gives the columns that
+/// col2im sums into the output.
+///
+impl<T: DataType> Tensor<T>
+{
+    ///
+    /// Transposed convolution of a sequence of [batch, channels, length].
+    ///
+    /// # Example
+    ///
+    /// let x = Tensor::<f32>::ones(&[8, 16, 50]);
+    /// let w = Tensor::<f32>::ones(&[16, 3, 4]);
+    ///
+    /// >>> x.conv_transpose1d(&w, None, [2], [1], [0], [1], 1).shape() = Shape { dims: [8, 3, 100] }
+    ///
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_conv_transpose1d(
+        &self,
+        weight: &Tensor<T>,
+        bias: Option<&Tensor<T>>,
+        stride: [usize; 1],
+        padding: [usize; 1],
+        output_padding: [usize; 1],
+        dilation: [usize; 1],
+        groups: usize,
+    ) -> Result<Tensor<T>>
+    {
+        self.try_conv_transpose(weight, bias, stride, padding, output_padding, dilation, groups)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn conv_transpose1d(
+        &self,
+        weight: &Tensor<T>,
+        bias: Option<&Tensor<T>>,
+        stride: [usize; 1],
+        padding: [usize; 1],
+        output_padding: [usize; 1],
+        dilation: [usize; 1],
+        groups: usize,
+    ) -> Tensor<T>
+    {
+        unwrap(self.try_conv_transpose1d(weight, bias, stride, padding, output_padding, dilation, groups))
+    }
+
+    ///
+    /// Transposed convolution of an image of [batch, channels, height, width].
+    ///
+    /// # Example
+    ///
+    /// let x = Tensor::<f32>::ones(&[8, 16, 7, 7]);
+    /// let w = Tensor::<f32>::ones(&[16, 8, 3, 3]);
+    ///
+    /// >>> x.conv_transpose2d(&w, None, [2, 2], [1, 1], [1, 1], [1, 1], 1).shape() = Shape { dims: [8, 8, 14, 14] }
+    ///
+    #[allow(clippy::too_many_arguments)]
+    pub fn try_conv_transpose2d(
+        &self,
+        weight: &Tensor<T>,
+        bias: Option<&Tensor<T>>,
+        stride: [usize; 2],
+        padding: [usize; 2],
+        output_padding: [usize; 2],
+        dilation: [usize; 2],
+        groups: usize,
+    ) -> Result<Tensor<T>>
+    {
+        self.try_conv_transpose(weight, bias, stride, padding, output_padding, dilation, groups)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn conv_transpose2d(
+        &self,
+        weight: &Tensor<T>,
+        bias: Option<&Tensor<T>>,
+        stride: [usize; 2],
+        padding: [usize; 2],
+        output_padding: [usize; 2],
+        dilation: [usize; 2],
+        groups: usize,
+    ) -> Tensor<T>
+    {
+        unwrap(self.try_conv_transpose2d(weight, bias, stride, padding, output_padding, dilation, groups))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn try_conv_transpose<const N: usize>(
+        &self,
+        weight: &Tensor<T>,
+        bias: Option<&Tensor<T>>,
+        stride: [usize; N],
+        padding: [usize; N],
+        output_padding: [usize; N],
+        dilation: [usize; N],
+        groups: usize,
+    ) -> Result<Tensor<T>>
+    {
+        let op = CONV_TRANSPOSE_OPS[N - 1];
+        let dims = self.shape().dims();
+        let w_dims = weight.shape().dims();
+        if dims.len() == N + 1
+        {
+            return self.try_unsqueeze(0)?
+                .try_conv_transpose(weight, bias, stride, padding, output_padding, dilation, groups)?
+                .try_squeeze(0);
+        }
+        if dims.len() != N + 2
+        {
+            let reason = format!("expected an input of {} or {} dims", N + 1, N + 2);
+            return Err(RuneError::invalid(op, dims, reason));
+        }
+        if w_dims.len() != N + 2
+        {
+            let reason = format!("expected a weight of {} dims", N + 2);
+            return Err(RuneError::invalid(op, w_dims, reason));
+        }
+        if groups == 0 || !w_dims[0].is_multiple_of(groups)
+        {
+            let reason = format!("in channels must be divisible by {} groups", groups);
+            return Err(RuneError::invalid(op, w_dims, reason));
+        }
+        if w_dims[0] != dims[1]
+        {
+            return Err(RuneError::shapes(op, &[dims, w_dims]));
+        }
+        if let Some(bias) = bias
+        {
+            if *bias.shape().dims() != [w_dims[1] * groups]
+            {
+                return Err(RuneError::shapes(op, &[w_dims, bias.shape().dims()]));
+            }
+        }
+
+        let output = conv_transpose_output(op, &dims[2..], &w_dims[2..], &stride, &padding, &output_padding, &dilation)?;
+        let (batch, in_channels, out_channels) = (dims[0], dims[1], w_dims[1] * groups);
+        let (group_in, group_out) = (in_channels / groups, w_dims[1]);
+        let window = Window::new(&output, &w_dims[2..], &dims[2..], &stride, &padding, &dilation);
+        let (k, l) = (group_out * window.kernel, window.positions);
+
+        let x = self.data().clone();
+        let w = weight.data().clone();
+        let mut y = vec![T::zero(); batch * out_channels * window.image];
+        {
+            let (xs, ws) = (contiguous(op, &x)?, contiguous(op, &w)?);
+            for n in 0..batch
+            {
+                for g in 0..groups
+                {
+                    let w_g = ArrayView2::from_shape((group_in, k), &ws[g * group_in * k..][..group_in * k]).unwrap();
+                    let x_g = ArrayView2::from_shape((group_in, l), &xs[(n * in_channels + g * group_in) * l..][..group_in * l]).unwrap();
+                    let cols = matmul_2d(&w_g.t(), &x_g);
+                    let offset = (n * out_channels + g * group_out) * window.image;
+                    window.col2im(cols.as_slice().unwrap(), group_out, &mut y[offset..]);
+                }
+            }
+        }
+        if let Some(bias) = bias
+        {
+            let bias = bias.data();
+            for (channel, y_c) in y.chunks_mut(window.image).enumerate()
+            {
+                let b = bias[[channel % out_channels]];
+                y_c.iter_mut().for_each(|v| *v += b);
+            }
+        }
+
+        let mut out_dims = vec![batch, out_channels];
+        out_dims.extend(output);
+        let data = ArrayD::from_shape_vec(IxDyn(&out_dims), y).unwrap();
+
+        let mut parents = vec![self.clone(), weight.clone()];
+        parents.extend(bias.cloned());
+        let has_bias = bias.is_some();
+        Ok(Tensor::from_op(data, parents, ||
+        {
+            // With cols = im2col(dY_g), dx_g = W_g @ cols and dW_g = x_g @ cols^T.
+            Box::new(move |grad|
+            {
+                let grad = grad.as_standard_layout();
+                let gs = grad.as_slice().unwrap();
+                let (xs, ws) = (x.as_slice().unwrap(), w.as_slice().unwrap());
+                let mut dx = vec![T::zero(); xs.len()];
+                let mut dw = vec![T::zero(); ws.len()];
+                for n in 0..batch
+                {
+                    for g in 0..groups
+                    {
+                        let cols = window.im2col(&gs[(n * out_channels + g * group_out) * window.image..], group_out);
+                        let cols = ArrayView2::from_shape((k, l), &cols).unwrap();
+                        let x_offset = (n * in_channels + g * group_in) * l;
+                        let w_g = &ws[g * group_in * k..][..group_in * k];
+                        let x_g = ArrayView2::from_shape((group_in, l), &xs[x_offset..][..group_in * l]).unwrap();
+
+                        gemm(group_in, k, l, w_g, cols.as_slice().unwrap(), &mut dx[x_offset..][..group_in * l]);
+                        let dw_g = matmul_2d(&x_g, &cols.t());
+                        dw[g * group_in * k..][..group_in * k].iter_mut()
+                            .zip(dw_g.iter())
+                            .for_each(|(a, &b)| *a += b);
+                    }
+                }
+
+                let mut grads = vec![
+                    ArrayD::from_shape_vec(x.raw_dim(), dx).unwrap(),
+                    ArrayD::from_shape_vec(w.raw_dim(), dw).unwrap(),
+                ];
+                if has_bias
+                {
+                    let mut db = vec![T::zero(); out_channels];
+                    for (channel, g_c) in gs.chunks(window.image).enumerate()
+                    {
+                        db[channel % out_channels] += g_c.iter().fold(T::zero(), |s, &v| s + v);
+                    }
+                    grads.push(ArrayD::from_shape_vec(IxDyn(&[out_channels]), db).unwrap());
+                }
+                grads
+            })
+        }))
+    }
+}
+
+///
+/// FUNCTIONAL UNIT TESTING
+///
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::autograd::gradcheck;
+    use crate::random::Generator;
+
+    fn tensor(dims: &[usize], seed: u64) -> Tensor<f64>
+    {
+        let mut t = Tensor::uniform_with(dims, -1.0, 1.0, &mut Generator::new(seed));
+        t.set_requires_grad(true);
+        t
+    }
+
+    ///
+    /// Direct convolution with four nested loops per output value.
+    ///
+    fn naive_conv2d(
+        x: &ArrayD<f64>,
+        w: &ArrayD<f64>,
+        stride: [usize; 2],
+        padding: [usize; 2],
+        dilation: [usize; 2],
+        groups: usize,
+    ) -> ArrayD<f64>
+    {
+        let (n, c, h, wd) = (x.shape()[0], x.shape()[1], x.shape()[2], x.shape()[3]);
+        let (o, cg, kh, kw) = (w.shape()[0], w.shape()[1], w.shape()[2], w.shape()[3]);
+        let oh = (h + 2 * padding[0] - dilation[0] * (kh - 1) - 1) / stride[0] + 1;
+        let ow = (wd + 2 * padding[1] - dilation[1] * (kw - 1) - 1) / stride[1] + 1;
+        let og = o / groups;
+        assert_eq!(c, cg * groups);
+
+        ArrayD::from_shape_fn(IxDyn(&[n, o, oh, ow]), |idx|
+        {
+            let (b, oc, i, j) = (idx[0], idx[1], idx[2], idx[3]);
+            let g = oc / og;
+            let mut sum = 0.0;
+            for ic in 0..cg
+            {
+                for p in 0..kh
+                {
+                    for q in 0..kw
+                    {
+                        let r = (i * stride[0] + p * dilation[0]) as isize - padding[0] as isize;
+                        let s = (j * stride[1] + q * dilation[1]) as isize - padding[1] as isize;
+                        if r >= 0 && s >= 0 && (r as usize) < h && (s as usize) < wd
+                        {
+                            sum += x[[b, g * cg + ic, r as usize, s as usize]] * w[[oc, ic, p, q]];
+                        }
+                    }
+                }
+            }
+            sum
+        })
+    }
+
+    #[test]
+    fn values()
+    {
+        let x = Tensor::new(ArrayD::from_shape_vec(IxDyn(&[1, 1, 5]), vec![1.0, 2.0, 3.0, 4.0, 5.0]).unwrap());
+        let w = Tensor::new(ArrayD::from_shape_vec(IxDyn(&[1, 1, 2]), vec![1.0, -1.0]).unwrap());
+        let b = Tensor::new(ArrayD::from_elem(IxDyn(&[1]), 10.0));
+
+        let y = x.conv1d(&w, Some(&b), [2], [1], [1], 1);
+        assert_eq!(y.data().as_slice().unwrap(), &[9.0, 9.0, 9.0]);
+
+        let y = x.conv1d(&w, None, [1], [0], [3], 1);
+        assert_eq!(y.data().as_slice().unwrap(), &[-3.0, -3.0]);
+    }
+
+    #[test]
+    fn matches_direct_convolution()
+    {
+        let cases = [
+            ([1, 1], [0, 0], [1, 1], 1),
+            ([2, 1], [1, 2], [1, 1], 1),
+            ([1, 2], [2, 1], [2, 1], 2),
+            ([3, 2], [1, 1], [1, 2], 4),
+        ];
+        for (stride, padding, dilation, groups) in cases
+        {
+            let x = tensor(&[2, 4, 7, 6], 0);
+            let w = tensor(&[8, 4 / groups, 3, 2], 1);
+            let y = x.conv2d(&w, None, stride, padding, dilation, groups);
+            let expected = naive_conv2d(&x.data(), &w.data(), stride, padding, dilation, groups);
+
+            assert_eq!(y.shape().dims(), &expected.shape().to_vec());
+            assert!(y.data().iter().zip(expected.iter()).all(|(a, b)| (a - b).abs() < 1e-12));
+        }
+    }
+
+    #[test]
+    fn shapes()
+    {
+        let x = Tensor::<f32>::ones(&[2, 6, 9, 8, 7]);
+        let w = Tensor::<f32>::ones(&[4, 3, 3, 3, 3]);
+        let y = x.conv3d(&w, None, [2, 1, 3], [1, 0, 1], [1, 2, 1], 2);
+        assert_eq!(*y.shape().dims(), vec![2, 4, 5, 4, 3]);
+
+        let x = Tensor::<f32>::ones(&[3, 10]);
+        let w = Tensor::<f32>::ones(&[5, 3, 3]);
+        assert_eq!(*x.conv1d(&w, None, [1], [1], [1], 1).shape().dims(), vec![5, 10]);
+    }
+
+    #[test]
+    fn invalid()
+    {
+        let x = Tensor::<f32>::ones(&[1, 4, 8, 8]);
+
+        assert!(x.try_conv2d(&Tensor::ones(&[2, 3, 3, 3]), None, [1, 1], [0, 0], [1, 1], 1).is_err());
+        assert!(x.try_conv2d(&Tensor::ones(&[3, 2, 3, 3]), None, [1, 1], [0, 0], [1, 1], 2).is_err());
+        assert!(x.try_conv2d(&Tensor::ones(&[2, 4, 9, 3]), None, [1, 1], [0, 0], [1, 1], 1).is_err());
+        assert!(x.try_conv2d(&Tensor::ones(&[2, 4, 3, 3]), None, [0, 1], [0, 0], [1, 1], 1).is_err());
+        assert!(x.try_conv2d(&Tensor::ones(&[2, 4, 3, 3]), Some(&Tensor::ones(&[3])), [1, 1], [0, 0], [1, 1], 1).is_err());
+        assert!(x.try_conv1d(&Tensor::ones(&[2, 4, 3]), None, [1], [0], [1], 1).is_err());
+        assert!(x.try_conv2d(&Tensor::ones(&[2, 4, 5, 5]), None, [1, 1], [0, 0], [2, 2], 1).is_err());
+        assert!(x.try_conv2d(&Tensor::ones(&[2, 4, 0, 3]), None, [1, 1], [0, 0], [1, 1], 1).is_err());
+    }
+
+    #[test]
+    fn gradients()
+    {
+        let inputs = [tensor(&[2, 4, 6], 2), tensor(&[6, 2, 3], 3), tensor(&[6], 4)];
+        let m = Tensor::new(tensor(&[2, 6, 3], 5).data().clone());
+        assert!(gradcheck(|t| t[0].conv1d(&t[1], Some(&t[2]), [2], [2], [2], 2).mul(&m), &inputs, 1e-3, 1e-6));
+
+        let inputs = [tensor(&[2, 3, 5, 4], 6), tensor(&[4, 3, 2, 3], 7), tensor(&[4], 8)];
+        let m = Tensor::new(tensor(&[2, 4, 3, 2], 9).data().clone());
+        assert!(gradcheck(|t| t[0].conv2d(&t[1], Some(&t[2]), [2, 1], [1, 0], [1, 1], 1).mul(&m), &inputs, 1e-3, 1e-6));
+
+        let inputs = [tensor(&[1, 2, 3, 4, 3], 10), tensor(&[2, 1, 2, 2, 2], 11)];
+        let m = Tensor::new(tensor(&[1, 2, 2, 2, 2], 12).data().clone());
+        assert!(gradcheck(|t| t[0].conv3d(&t[1], None, [1, 2, 1], [0, 0, 0], [1, 1, 1], 2).mul(&m), &inputs, 1e-3, 1e-6));
+    }
+
+    #[test]
+    fn transpose_values()
+    {
+        let x = Tensor::new(ArrayD::from_shape_vec(IxDyn(&[1, 1, 2]), vec![1.0, 2.0]).unwrap());
+        let w = Tensor::new(ArrayD::from_elem(IxDyn(&[1, 1, 3]), 1.0));
+        let b = Tensor::new(ArrayD::from_elem(IxDyn(&[1]), 0.5));
+
+        let y = x.conv_transpose1d(&w, Some(&b), [2], [0], [0], [1], 1);
+        assert_eq!(y.data().as_slice().unwrap(), &[1.5, 1.5, 3.5, 2.5, 2.5]);
+
+        let y = x.conv_transpose1d(&w, None, [2], [1], [1], [1], 1);
+        assert_eq!(y.data().as_slice().unwrap(), &[1.0, 3.0, 2.0, 2.0]);
+    }
+
+    #[test]
+    fn transpose_is_adjoint()
+    {
+        // <conv(x, w), y> = <x, conv_transpose(y, w)> for the same geometry.
+        let cases = [
+            ([2, 2], [1, 1], [1, 1], [1, 1], 1),
+            ([3, 1], [0, 1], [2, 0], [1, 2], 2),
+            ([1, 2], [2, 0], [0, 1], [2, 1], 4),
+        ];
+        for (stride, padding, output_padding, dilation, groups) in cases
+        {
+            let x = tensor(&[2, 4, 8, 7], 13);
+            let w = tensor(&[8, 4 / groups, 3, 2], 14);
+            let conv = x.conv2d(&w, None, stride, padding, dilation, groups);
+            let y = tensor(conv.shape().dims(), 15);
+            let transposed = y.conv_transpose2d(&w, None, stride, padding, output_padding, dilation, groups);
+
+            assert_eq!(transposed.shape().dims(), x.shape().dims());
+            let lhs = (&*conv.data() * &*y.data()).sum();
+            let rhs = (&*x.data() * &*transposed.data()).sum();
+            assert!((lhs - rhs).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn transpose_shapes()
+    {
+        let x = Tensor::<f32>::ones(&[8, 16, 7, 7]);
+        let w = Tensor::<f32>::ones(&[16, 4, 3, 3]);
+        let y = x.conv_transpose2d(&w, None, [2, 2], [1, 1], [1, 0], [1, 1], 2);
+        assert_eq!(*y.shape().dims(), vec![8, 8, 14, 13]);
+
+        let x = Tensor::<f32>::ones(&[4, 10]);
+        let w = Tensor::<f32>::ones(&[4, 3, 3]);
+        assert_eq!(*x.conv_transpose1d(&w, None, [1], [0], [0], [2], 1).shape().dims(), vec![3, 14]);
+    }
+
+    #[test]
+    fn transpose_invalid()
+    {
+        let x = Tensor::<f32>::ones(&[1, 4, 5]);
+
+        assert!(x.try_conv_transpose1d(&Tensor::ones(&[3, 2, 3]), None, [1], [0], [0], [1], 1).is_err());
+        assert!(x.try_conv_transpose1d(&Tensor::ones(&[4, 2, 3]), None, [1], [0], [0], [1], 3).is_err());
+        assert!(x.try_conv_transpose1d(&Tensor::ones(&[4, 2, 3]), None, [2], [0], [2], [1], 1).is_err());
+        assert!(x.try_conv_transpose1d(&Tensor::ones(&[4, 2, 3]), None, [1], [4], [0], [1], 1).is_err());
+        assert!(x.try_conv_transpose1d(&Tensor::ones(&[4, 2, 3]), Some(&Tensor::ones(&[4])), [1], [0], [0], [1], 1).is_err());
+        assert!(x.try_conv_transpose2d(&Tensor::ones(&[4, 2, 3, 3]), None, [1, 1], [0, 0], [0, 0], [1, 1], 1).is_err());
+        assert!(Tensor::<f32>::ones(&[1, 4, 0]).try_conv_transpose1d(&Tensor::ones(&[4, 2, 3]), None, [1], [0], [0], [1], 1).is_err());
+    }
+
+    #[test]
+    fn transpose_gradients()
+    {
+        let inputs = [tensor(&[2, 4, 5], 16), tensor(&[4, 3, 3], 17), tensor(&[6], 18)];
+        let m = Tensor::new(tensor(&[2, 6, 10], 19).data().clone());
+        let f = |t: &[Tensor<f64>]| t[0].conv_transpose1d(&t[1], Some(&t[2]), [2], [1], [1], [1], 2).mul(&m);
+        assert!(gradcheck(f, &inputs, 1e-3, 1e-6));
+
+        let inputs = [tensor(&[2, 3, 3, 4], 20), tensor(&[3, 2, 2, 3], 21), tensor(&[2], 22)];
+        let m = Tensor::new(tensor(&[2, 2, 7, 14], 23).data().clone());
+        let f = |t: &[Tensor<f64>]| t[0].conv_transpose2d(&t[1], Some(&t[2]), [2, 3], [0, 1], [1, 2], [1, 2], 1).mul(&m);
+        assert!(gradcheck(f, &inputs, 1e-3, 1e-6));
+    }
+}
@@ -0,0 +1,297 @@
+//
+// MIT License
+// 
+// Copyright (c) 2023 Wilhelm Ågren
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// 
+// File created: 2026-10-17
+// Last updated: 2026-10-17
+//
+
+use crate::datatype::DataType;
+
+use ndarray::Array2;
+use ndarray::ArrayView2;
+
+use std::thread;
+
+///
+/// Dimensions of the register tile computed by the micro kernel, the cache
+/// blocks of the packed operands and the amount of multiply-adds a thread
+/// should at least be given before it is worth spawning.
+///
+const MR: usize = 4;
+const NR: usize = 8;
+const MC: usize = 64;
+const KC: usize = 256;
+const MIN_WORK_PER_THREAD: usize = 1 << 16;
+
+///
+/// General matrix multiplication c = a @ b on row-major buffers, where a is
+/// (m, k), b is (k, n) and c is (m, n). Any previous contents of c are 
+/// overwritten.
+///
+/// b is packed once into column panels of width NR, blocked by KC along the
+/// inner dimension, and shared by all threads. The rows of c are split into
+/// one chunk per thread and every thread packs its own MC x KC blocks of a 
+/// into row panels of height MR. The micro kernel then computes MR x NR 
+/// tiles of c from one row panel and one column panel, which both fit in
+/// L1 cache and which the compiler is able to vectorize.
+///
+pub fn gemm<T: DataType>(m: usize, k: usize, n: usize, a: &[T], b: &[T], c: &mut [T])
+{
+    assert_eq!(a.len(), m * k, "Expected lhs buffer of length {}", m * k);
+    assert_eq!(b.len(), k * n, "Expected rhs buffer of length {}", k * n);
+    assert_eq!(c.len(), m * n, "Expected output buffer of length {}", m * n);
+
+    c.fill(T::zero());
+    if m == 0 || n == 0 || k == 0
+    {
+        return;
+    }
+
+    let packed_b = pack_b(k, n, b);
+    let threads = num_threads(m, k, n);
+    let rows_per_thread = m.div_ceil(threads).div_ceil(MR) * MR;
+
+    if threads == 1
+    {
+        gemm_rows(0, m, k, n, a, &packed_b, c);
+        return;
+    }
+
+    thread::scope(|scope|
+    {
+        for (idx, chunk) in c.chunks_mut(rows_per_thread * n).enumerate()
+        {
+            let packed_b = &packed_b;
+            let rows = chunk.len() / n;
+            scope.spawn(move || gemm_rows(idx * rows_per_thread, rows, k, n, a, packed_b, chunk));
+        }
+    });
+}
+
+///
+/// Multiply two matrix views of any memory layout, returning a new matrix
+/// in standard layout.
+///
+pub fn matmul_2d<T: DataType>(a: &ArrayView2<T>, b: &ArrayView2<T>) -> Array2<T>
+{
+    let (m, k) = a.dim();
+    let (k_b, n) = b.dim();
+    assert_eq!(k, k_b, "Inner dimensions do not match, ({}, {}) @ ({}, {})", m, k, k_b, n);
+
+    let a = a.as_standard_layout();
+    let b = b.as_standard_layout();
+    let mut c = vec![T::zero(); m * n];
+    gemm(m, k, n, a.as_slice().unwrap(), b.as_slice().unwrap(), &mut c);
+    Array2::from_shape_vec((m, n), c).unwrap()
+}
+
+fn num_threads(m: usize, k: usize, n: usize) -> usize
+{
+    let available = thread::available_parallelism().map(|t| t.get()).unwrap_or(1);
+    let by_work = (m * k * n / MIN_WORK_PER_THREAD).max(1);
+    let by_rows = m.div_ceil(MR);
+    available.min(by_work).min(by_rows)
+}
+
+///
+/// Pack b into column panels. For every KC block along the inner dimension
+/// the panels are laid out one after the other, each one being kc rows of NR
+/// contiguous values. Columns past n are padded with zeros.
+///
+fn pack_b<T: DataType>(k: usize, n: usize, b: &[T]) -> Vec<T>
+{
+    let n_padded = n.div_ceil(NR) * NR;
+    let mut packed = vec![T::zero(); k * n_padded];
+    let mut offset = 0;
+
+    for pc in (0..k).step_by(KC)
+    {
+        let kc = KC.min(k - pc);
+        for jr in (0..n).step_by(NR)
+        {
+            let nr = NR.min(n - jr);
+            for p in 0..kc
+            {
+                let row = &b[(pc + p) * n + jr..(pc + p) * n + jr + nr];
+                packed[offset + p * NR..offset + p * NR + nr].copy_from_slice(row);
+            }
+            offset += kc * NR;
+        }
+    }
+
+    packed
+}
+
+///
+/// Pack an mc x kc block of a, starting at (row, pc), into row panels of 
+/// height MR laid out as kc columns of MR contiguous values. Rows past the
+/// end of the block are padded with zeros.
+///
+fn pack_a<T: DataType>(a: &[T], k: usize, row: usize, mc: usize, pc: usize, kc: usize, packed: &mut [T])
+{
+    for (panel, ir) in (0..mc).step_by(MR).enumerate()
+    {
+        let mr = MR.min(mc - ir);
+        let dst = &mut packed[panel * kc * MR..(panel + 1) * kc * MR];
+        for i in 0..MR
+        {
+            for p in 0..kc
+            {
+                dst[p * MR + i] = match i < mr
+                {
+                    true => a[(row + ir + i) * k + pc + p],
+                    false => T::zero(),
+                };
+            }
+        }
+    }
+}
+
+///
+/// Compute `rows` rows of c, starting at row `row0` of a. The chunk of c only
+/// holds the rows that this call is responsible for.
+///
+fn gemm_rows<T: DataType>(
+    row0: usize,
+    rows: usize,
+    k: usize,
+    n: usize,
+    a: &[T],
+    packed_b: &[T],
+    c: &mut [T],
+)
+{
+    let n_padded = n.div_ceil(NR) * NR;
+    let mut packed_a = vec![T::zero(); MC.div_ceil(MR) * MR * KC];
+
+    for pc in (0..k).step_by(KC)
+    {
+        let kc = KC.min(k - pc);
+        let b_block = &packed_b[pc * n_padded..(pc + kc) * n_padded];
+
+        for ic in (0..rows).step_by(MC)
+        {
+            let mc = MC.min(rows - ic);
+            pack_a(a, k, row0 + ic, mc, pc, kc, &mut packed_a);
+
+            for (panel_b, jr) in (0..n).step_by(NR).enumerate()
+            {
+                let nr = NR.min(n - jr);
+                let b_panel = &b_block[panel_b * kc * NR..(panel_b + 1) * kc * NR];
+
+                for (panel_a, ir) in (0..mc).step_by(MR).enumerate()
+                {
+                    let mr = MR.min(mc - ir);
+                    let a_panel = &packed_a[panel_a * kc * MR..(panel_a + 1) * kc * MR];
+                    let tile = kernel(a_panel, b_panel);
+
+                    for (i, tile_row) in tile.iter().enumerate().take(mr)
+                    {
+                        let c_row = &mut c[(ic + ir + i) * n + jr..(ic + ir + i) * n + jr + nr];
+                        for (c_ij, &t_ij) in c_row.iter_mut().zip(tile_row.iter())
+                        {
+                            *c_ij += t_ij;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+///
+/// The MR x NR register tile. Accumulates the outer products of the packed
+/// columns of a with the packed rows of b.
+///
+#[inline(always)]
+fn kernel<T: DataType>(a_panel: &[T], b_panel: &[T]) -> [[T; NR]; MR]
+{
+    let mut tile = [[T::zero(); NR]; MR];
+    for (a, b) in a_panel.chunks_exact(MR).zip(b_panel.chunks_exact(NR))
+    {
+        for i in 0..MR
+        {
+            for j in 0..NR
+            {
+                tile[i][j] += a[i] * b[j];
+            }
+        }
+    }
+    tile
+}
+
+///
+/// FUNCTIONAL UNIT TESTING
+///
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn naive(m: usize, k: usize, n: usize, a: &[f64], b: &[f64]) -> Vec<f64>
+    {
+        let mut c = vec![0.0; m * n];
+        for i in 0..m
+        {
+            for j in 0..n
+            {
+                for p in 0..k
+                {
+                    c[i * n + j] += a[i * k + p] * b[p * n + j];
+                }
+            }
+        }
+        c
+    }
+
+    #[test]
+    fn matches_naive()
+    {
+        // Sizes that are not multiples of any of the block sizes.
+        for &(m, k, n) in &[(1, 1, 1), (3, 5, 7), (67, 300, 13), (130, 513, 70)]
+        {
+            let a: Vec<f64> = (0..m * k).map(|i| (i % 7) as f64 - 3.0).collect();
+            let b: Vec<f64> = (0..k * n).map(|i| (i % 5) as f64 * 0.5).collect();
+            let mut c = vec![1.0; m * n];
+            gemm(m, k, n, &a, &b, &mut c);
+            assert_eq!(c, naive(m, k, n, &a, &b));
+        }
+    }
+
+    #[test]
+    fn empty()
+    {
+        let mut c = vec![1.0f32; 6];
+        gemm(2, 0, 3, &[], &[], &mut c);
+        assert_eq!(c, vec![0.0; 6]);
+    }
+
+    #[test]
+    fn layouts()
+    {
+        let a = Array2::from_shape_fn((4, 3), |(i, j)| (i * 3 + j) as f32);
+        let b = Array2::from_shape_fn((4, 5), |(i, j)| (i + j) as f32);
+        let c = matmul_2d(&a.t(), &b.view());
+        assert_eq!(c, a.t().dot(&b));
+    }
+}
@@ -0,0 +1,476 @@
+//
+// MIT License
+// 
+// Copyright (c) 2023 Wilhelm Ågren
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// 
+// File created: 2026-10-17
+// Last updated: 2026-10-17
+//
+
+use crate::datatype::DataType;
+use crate::error::unwrap;
+use crate::error::Result;
+use crate::error::RuneError;
+use crate::tensor::Tensor;
+
+use ndarray::ArrayD;
+use ndarray::Axis;
+use ndarray::IxDyn;
+use ndarray::Slice;
+
+use std::collections::HashMap;
+
+///
+/// Slicing and indexing. The backward rules scatter the gradient of the 
+/// selected values back into a zero gradient of the input shape. When the 
+/// same input value is selected several times its gradients add up.
+///
+impl<T: DataType> Tensor<T>
+{
+    ///
+    /// Slice every axis by a range with a step, see `ndarray::Slice`. Axes 
+    /// past the given slices are kept whole. Negative start and end values
+    /// count from the back of the axis.
+    ///
+    /// # Example
+    ///
+    /// let t = Tensor::<f32>::ones(&[32, 3, 64, 64]);
+    /// let s = t.slice(&[Slice::from(..16), Slice::from(..), Slice::new(0, None, 2)]);
+    ///
+    /// >>> s.shape() = Shape { dims: [16, 3, 32, 64] }
+    ///
+    pub fn try_slice(&self, slices: &[Slice]) -> Result<Tensor<T>>
+    {
+        let dims = self.shape().dims();
+        if slices.len() > dims.len()
+        {
+            let reason = format!("could not slice by {} slices", slices.len());
+            return Err(RuneError::invalid("slice", dims, reason));
+        }
+        for (&slice, &len) in slices.iter().zip(dims.iter())
+        {
+            let resolve = |i: isize| if i < 0 { i + len as isize } else { i };
+            let start = resolve(slice.start);
+            let end = slice.end.map_or(len as isize, resolve);
+            if slice.step == 0 || start < 0 || start > len as isize || end < 0 || end > len as isize
+            {
+                let reason = format!("slice {:?} is out of bounds", slice);
+                return Err(RuneError::invalid("slice", dims, reason));
+            }
+        }
+
+        let slices = slices.to_vec();
+        let slicer = move |axis: usize| slices.get(axis).copied().unwrap_or(Slice::from(..));
+        let data = self.data().slice_each_axis(|ax| slicer(ax.axis.index())).to_owned();
+        Ok(Tensor::from_op(data, vec![self.clone()], ||
+        {
+            let dims = self.shape().dims().clone();
+            Box::new(move |grad|
+            {
+                let mut d_input = ArrayD::<T>::zeros(IxDyn(&dims));
+                d_input.slice_each_axis_mut(|ax| slicer(ax.axis.index())).assign(grad);
+                vec![d_input]
+            })
+        }))
+    }
+
+    pub fn slice(&self, slices: &[Slice]) -> Tensor<T>
+    {
+        unwrap(self.try_slice(slices))
+    }
+
+    ///
+    /// Select the entries at `indices` along `axis`, an index may be given
+    /// more than once. This is the lookup of an embedding table.
+    ///
+    pub fn try_index_select(&self, axis: usize, indices: &[usize]) -> Result<Tensor<T>>
+    {
+        let dims = self.shape().dims();
+        if axis >= dims.len()
+        {
+            let reason = format!("axis {} is out of bounds", axis);
+            return Err(RuneError::invalid("index_select", dims, reason));
+        }
+        if let Some(&index) = indices.iter().find(|&&i| i >= dims[axis])
+        {
+            return Err(RuneError::IndexOutOfBounds { op: "index_select", index, size: dims[axis] });
+        }
+
+        let data = self.data().select(Axis(axis), indices);
+        Ok(Tensor::from_op(data, vec![self.clone()], ||
+        {
+            let dims = dims.clone();
+            let indices = indices.to_vec();
+            Box::new(move |grad|
+            {
+                let mut d_input = ArrayD::<T>::zeros(IxDyn(&dims));
+                for (&i, g) in indices.iter().zip(grad.axis_iter(Axis(axis)))
+                {
+                    let mut lane = d_input.index_axis_mut(Axis(axis), i);
+                    lane += &g;
+                }
+                vec![d_input]
+            })
+        }))
+    }
+
+    pub fn index_select(&self, axis: usize, indices: &[usize]) -> Tensor<T>
+    {
+        unwrap(self.try_index_select(axis, indices))
+    }
+
+    ///
+    /// Gather values along `axis` by an index array of the same number of
+    /// dims. The output has the shape of `index` and for axis 1 of a 3-D 
+    /// tensor out[i][j][k] = input[i][index[i][j][k]][k].
+    ///
+    pub fn try_gather(&self, axis: usize, index: &ArrayD<usize>) -> Result<Tensor<T>>
+    {
+        self.check_index("gather", axis, index, self.shape().dims())?;
+
+        let input = self.data();
+        let data = ArrayD::from_shape_fn(index.raw_dim(), |pos| input[along(pos, axis, index)]);
+        Ok(Tensor::from_op(data, vec![self.clone()], ||
+        {
+            let dims = self.shape().dims().clone();
+            let index = index.clone();
+            Box::new(move |grad|
+            {
+                let mut d_input = ArrayD::<T>::zeros(IxDyn(&dims));
+                for (pos, &g) in grad.indexed_iter()
+                {
+                    d_input[along(pos, axis, &index)] += g;
+                }
+                vec![d_input]
+            })
+        }))
+    }
+
+    pub fn gather(&self, axis: usize, index: &ArrayD<usize>) -> Tensor<T>
+    {
+        unwrap(self.try_gather(axis, index))
+    }
+
+    ///
+    /// The reverse of `gather`, returns a copy of this tensor where the 
+    /// values of `src` are written to the positions given by `index`, for 
+    /// axis 1 of a 3-D tensor out[i][index[i][j][k]][k] = src[i][j][k]. If
+    /// an index repeats, the last value written wins.
+    ///
+    pub fn try_scatter(&self, axis: usize, index: &ArrayD<usize>, src: &Tensor<T>) -> Result<Tensor<T>>
+    {
+        self.check_index("scatter", axis, index, self.shape().dims())?;
+        let fits = index.ndim() == src.shape().ndim()
+            && index.shape().iter().zip(src.shape().dims().iter()).all(|(&i, &s)| i <= s);
+        if !fits
+        {
+            return Err(RuneError::shapes("scatter", &[index.shape(), src.shape().dims()]));
+        }
+
+        let mut data = self.data().clone();
+        let source = src.data();
+        for (pos, _) in index.indexed_iter()
+        {
+            let value = source[&pos];
+            data[along(pos, axis, index)] = value;
+        }
+
+        Ok(Tensor::from_op(data, vec![self.clone(), src.clone()], ||
+        {
+            let index = index.clone();
+            let src_dims = src.shape().dims().clone();
+            Box::new(move |grad|
+            {
+                // Only the last write to a position reaches the output, so
+                // the other sources of that position get no gradient.
+                let mut d_input = grad.clone();
+                let mut d_src = ArrayD::<T>::zeros(IxDyn(&src_dims));
+                let mut written = HashMap::new();
+                for (pos, _) in index.indexed_iter()
+                {
+                    written.insert(along(pos.clone(), axis, &index), pos);
+                }
+                for (target, pos) in written
+                {
+                    d_src[pos] = grad[&target];
+                    d_input[target] = T::zero();
+                }
+                vec![d_input, d_src]
+            })
+        }))
+    }
+
+    pub fn scatter(&self, axis: usize, index: &ArrayD<usize>, src: &Tensor<T>) -> Tensor<T>
+    {
+        unwrap(self.try_scatter(axis, index, src))
+    }
+
+    ///
+    /// Select the values where `mask` is true into a 1-D tensor, in the 
+    /// logical order of the tensor. The mask must broadcast to our shape.
+    ///
+    pub fn try_masked_select(&self, mask: &ArrayD<bool>) -> Result<Tensor<T>>
+    {
+        let mask = self.broadcast_mask("masked_select", mask)?;
+        let values: Vec<T> = self.data()
+            .iter()
+            .zip(mask.iter())
+            .filter(|(_, &m)| m)
+            .map(|(&v, _)| v)
+            .collect();
+        let data = ArrayD::from_shape_vec(IxDyn(&[values.len()]), values).unwrap();
+        Ok(Tensor::from_op(data, vec![self.clone()], ||
+        {
+            let dims = self.shape().dims().clone();
+            Box::new(move |grad|
+            {
+                let mut d_input = ArrayD::<T>::zeros(IxDyn(&dims));
+                let targets = d_input.iter_mut().zip(mask.iter()).filter(|(_, &m)| m);
+                for ((d, _), &g) in targets.zip(grad.iter())
+                {
+                    *d = g;
+                }
+                vec![d_input]
+            })
+        }))
+    }
+
+    pub fn masked_select(&self, mask: &ArrayD<bool>) -> Tensor<T>
+    {
+        unwrap(self.try_masked_select(mask))
+    }
+
+    ///
+    /// Replace the values where `mask` is true by `value`. The mask must 
+    /// broadcast to our shape, the filled positions get no gradient.
+    ///
+    pub fn try_masked_fill(&self, mask: &ArrayD<bool>, value: T) -> Result<Tensor<T>>
+    {
+        let mask = self.broadcast_mask("masked_fill", mask)?;
+        let mut data = self.data().clone();
+        data.zip_mut_with(&mask, |v, &m| if m { *v = value; });
+        Ok(Tensor::from_op(data, vec![self.clone()], ||
+        {
+            Box::new(move |grad|
+            {
+                let mut d_input = grad.clone();
+                d_input.zip_mut_with(&mask, |g, &m| if m { *g = T::zero(); });
+                vec![d_input]
+            })
+        }))
+    }
+
+    pub fn masked_fill(&self, mask: &ArrayD<bool>, value: T) -> Tensor<T>
+    {
+        unwrap(self.try_masked_fill(mask, value))
+    }
+
+    fn broadcast_mask(&self, op: &'static str, mask: &ArrayD<bool>) -> Result<ArrayD<bool>>
+    {
+        match mask.broadcast(IxDyn(self.shape().dims()))
+        {
+            Some(mask) => Ok(mask.to_owned()),
+            None => Err(RuneError::shapes(op, &[mask.shape(), self.shape().dims()])),
+        }
+    }
+
+    ///
+    /// An index for gather and scatter must have as many dims as the input,
+    /// be no larger than `dims` along every other axis and point inside the
+    /// input along `axis`.
+    ///
+    fn check_index(&self, op: &'static str, axis: usize, index: &ArrayD<usize>, dims: &[usize])
+        -> Result<()>
+    {
+        let input = self.shape().dims();
+        if axis >= input.len()
+        {
+            return Err(RuneError::invalid(op, input, format!("axis {} is out of bounds", axis)));
+        }
+        let fits = index.ndim() == input.len()
+            && index.shape().iter().zip(dims.iter()).enumerate().all(|(a, (&i, &d))| a == axis || i <= d);
+        if !fits
+        {
+            return Err(RuneError::shapes(op, &[index.shape(), dims]));
+        }
+        match index.iter().find(|&&i| i >= input[axis])
+        {
+            Some(&index) => Err(RuneError::IndexOutOfBounds { op, index, size: input[axis] }),
+            None => Ok(()),
+        }
+    }
+}
+
+///
+/// Replace the coordinate along `axis` of `pos` by the index stored at `pos`.
+///
+fn along(mut pos: IxDyn, axis: usize, index: &ArrayD<usize>) -> IxDyn
+{
+    pos[axis] = index[&pos];
+    pos
+}
+
+///
+/// FUNCTIONAL UNIT TESTING
+///
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::autograd::gradcheck;
+
+    fn arange(dims: &[usize]) -> Tensor<f64>
+    {
+        let len = dims.iter().product::<usize>();
+        let data = (0..len).map(|i| i as f64).collect();
+        let mut t = Tensor::new(ArrayD::from_shape_vec(IxDyn(dims), data).unwrap());
+        t.set_requires_grad(true);
+        t
+    }
+
+    fn index(dims: &[usize], values: Vec<usize>) -> ArrayD<usize>
+    {
+        ArrayD::from_shape_vec(IxDyn(dims), values).unwrap()
+    }
+
+    ///
+    /// Weight the output by position so that misplaced gradients show.
+    ///
+    fn weighted(t: Tensor<f64>) -> Tensor<f64>
+    {
+        let weight = arange(t.shape().dims()).data().mapv(|v| v + 1.0);
+        t.mul(&Tensor::new(weight))
+    }
+
+    #[test]
+    fn slice()
+    {
+        let t = arange(&[4, 5]);
+        let s = t.slice(&[Slice::new(1, Some(-1), 1), Slice::new(0, None, 2)]);
+
+        assert_eq!(*s.shape().dims(), vec![2, 3]);
+        assert_eq!(s.data().as_slice().unwrap(), &[5.0, 7.0, 9.0, 10.0, 12.0, 14.0]);
+
+        s.sum_all().backward();
+        let expected: Vec<f64> = (0..20)
+            .map(|i| ((1..3).contains(&(i / 5)) && i % 5 % 2 == 0) as usize as f64)
+            .collect();
+        assert_eq!(t.grad().as_slice().unwrap(), expected.as_slice());
+    }
+
+    #[test]
+    fn index_select()
+    {
+        let t = arange(&[3, 2]);
+        let s = t.index_select(0, &[2, 0, 2]);
+
+        assert_eq!(s.data().as_slice().unwrap(), &[4.0, 5.0, 0.0, 1.0, 4.0, 5.0]);
+
+        s.sum_all().backward();
+        assert_eq!(t.grad().as_slice().unwrap(), &[1.0, 1.0, 0.0, 0.0, 2.0, 2.0]);
+    }
+
+    #[test]
+    fn gather()
+    {
+        // [[0, 1, 2],
+        //  [3, 4, 5]]
+        let t = arange(&[2, 3]);
+        let g = t.gather(1, &index(&[2, 2], vec![2, 2, 0, 1]));
+
+        assert_eq!(g.data().as_slice().unwrap(), &[2.0, 2.0, 3.0, 4.0]);
+
+        g.sum_all().backward();
+        assert_eq!(t.grad().as_slice().unwrap(), &[0.0, 0.0, 2.0, 1.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn scatter()
+    {
+        let t = arange(&[2, 3]);
+        let src = arange(&[1, 3]).add(&Tensor::ones(&[1]));
+        let s = t.scatter(0, &index(&[1, 3], vec![1, 0, 1]), &src);
+
+        assert_eq!(s.data().as_slice().unwrap(), &[0.0, 2.0, 2.0, 1.0, 4.0, 3.0]);
+    }
+
+    #[test]
+    fn masks()
+    {
+        let t = arange(&[2, 3]);
+        let mask = index(&[1, 3], vec![1, 0, 1]).mapv(|m| m == 1);
+
+        let s = t.masked_select(&mask);
+        assert_eq!(s.data().as_slice().unwrap(), &[0.0, 2.0, 3.0, 5.0]);
+
+        let f = t.masked_fill(&mask, -1.0);
+        assert_eq!(f.data().as_slice().unwrap(), &[-1.0, 1.0, -1.0, -1.0, 4.0, -1.0]);
+
+        f.sum_all().backward();
+        assert_eq!(t.grad().as_slice().unwrap(), &[0.0, 1.0, 0.0, 0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn gather_out_of_bounds()
+    {
+        let _g = arange(&[2, 3]).gather(1, &index(&[2, 1], vec![0, 3]));
+    }
+
+    #[test]
+    fn fallible()
+    {
+        let t = arange(&[2, 3]);
+        let mask = ArrayD::from_elem(IxDyn(&[3, 1]), true);
+
+        assert_eq!(
+            t.try_gather(1, &index(&[2, 1], vec![0, 3])).err(),
+            Some(RuneError::IndexOutOfBounds { op: "gather", index: 3, size: 3 }),
+        );
+        assert_eq!(
+            t.try_index_select(0, &[1, 2]).err(),
+            Some(RuneError::IndexOutOfBounds { op: "index_select", index: 2, size: 2 }),
+        );
+        assert!(t.try_slice(&[Slice::from(..), Slice::from(1..4)]).is_err());
+        assert!(t.try_slice(&[Slice::from(-2..)]).is_ok());
+        assert!(t.try_scatter(0, &index(&[1, 3], vec![0, 1, 1]), &arange(&[1, 2])).is_err());
+        assert!(t.try_masked_fill(&mask, 0.0).is_err());
+    }
+
+    #[test]
+    fn gradients()
+    {
+        let x = [arange(&[3, 4])];
+        let i = index(&[2, 4], vec![2, 0, 1, 1, 0, 0, 2, 1]);
+        let m = index(&[3, 1], vec![0, 1, 1]).mapv(|m| m == 1);
+
+        assert!(gradcheck(|t| weighted(t[0].slice(&[Slice::new(0, None, 2)])), &x, 1e-3, 1e-6));
+        assert!(gradcheck(|t| weighted(t[0].index_select(1, &[3, 0, 3])), &x, 1e-3, 1e-6));
+        assert!(gradcheck(|t| weighted(t[0].gather(0, &i)), &x, 1e-3, 1e-6));
+        assert!(gradcheck(|t| weighted(t[0].masked_select(&m)), &x, 1e-3, 1e-6));
+        assert!(gradcheck(|t| weighted(t[0].masked_fill(&m, 2.0)), &x, 1e-3, 1e-6));
+
+        let src = arange(&[2, 4]);
+        let inputs = [x[0].clone(), src];
+        let i = index(&[2, 4], vec![2, 0, 1, 1, 0, 0, 2, 1]);
+        assert!(gradcheck(|t| weighted(t[0].scatter(0, &i, &t[1])), &inputs, 1e-3, 1e-6));
+    }
+}
@@ -0,0 +1,288 @@
+//
+// MIT License
+// 
+// Copyright (c) 2023 Wilhelm Ågren
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// 
+// File created: 2026-10-17
+// Last updated: 2026-10-17
+//
+
+use crate::datatype::DataType;
+use crate::error::unwrap;
+use crate::error::Result;
+use crate::error::RuneError;
+use crate::shape::Shape;
+use crate::tensor::Tensor;
+
+use ndarray::Axis;
+use ndarray::Slice;
+
+///
+/// Joining and splitting lists of tensors. Gradients of a join are split
+/// back into the pieces, gradients of a split are scattered back into the
+/// input through `slice`.
+///
+impl<T: DataType> Tensor<T>
+{
+    ///
+    /// Concatenate tensors along an existing axis. All tensors must agree 
+    /// on every other dim.
+    ///
+    /// # Example
+    ///
+    /// let a = Tensor::<f32>::ones(&[32, 3, 8]);
+    /// let b = Tensor::<f32>::ones(&[32, 5, 8]);
+    ///
+    /// >>> Tensor::cat(&[a, b], 1).shape() = Shape { dims: [32, 8, 8] }
+    ///
+    pub fn try_cat(tensors: &[Tensor<T>], axis: usize) -> Result<Tensor<T>>
+    {
+        let shapes: Vec<&Shape> = tensors.iter().map(|t| t.shape()).collect();
+        if Shape::concat(&shapes, axis).is_none()
+        {
+            let dims: Vec<&[usize]> = shapes.iter().map(|s| s.dims().as_slice()).collect();
+            return Err(RuneError::shapes("cat", &dims));
+        }
+
+        let data: Vec<_> = tensors.iter().map(|t| t.data()).collect();
+        let views: Vec<_> = data.iter().map(|d| d.view()).collect();
+        let data = ndarray::concatenate(Axis(axis), &views).unwrap();
+        Ok(Tensor::from_op(data, tensors.to_vec(), ||
+        {
+            let sizes: Vec<usize> = shapes.iter().map(|s| s.dims()[axis]).collect();
+            Box::new(move |grad|
+            {
+                let mut start = 0;
+                sizes.iter()
+                    .map(|&size|
+                    {
+                        let slice = Slice::from(start..start + size);
+                        start += size;
+                        grad.slice_axis(Axis(axis), slice).to_owned()
+                    })
+                    .collect()
+            })
+        }))
+    }
+
+    pub fn cat(tensors: &[Tensor<T>], axis: usize) -> Tensor<T>
+    {
+        unwrap(Tensor::try_cat(tensors, axis))
+    }
+
+    ///
+    /// Join tensors of equal shape along a new axis inserted at `axis`.
+    ///
+    pub fn try_stack(tensors: &[Tensor<T>], axis: usize) -> Result<Tensor<T>>
+    {
+        let shapes: Vec<&Shape> = tensors.iter().map(|t| t.shape()).collect();
+        if shapes.iter().any(|s| *s != shapes[0])
+        {
+            let dims: Vec<&[usize]> = shapes.iter().map(|s| s.dims().as_slice()).collect();
+            return Err(RuneError::shapes("stack", &dims));
+        }
+        let unsqueezed = tensors.iter()
+            .map(|t| t.try_unsqueeze(axis))
+            .collect::<Result<Vec<Tensor<T>>>>()?;
+        Tensor::try_cat(&unsqueezed, axis)
+    }
+
+    pub fn stack(tensors: &[Tensor<T>], axis: usize) -> Tensor<T>
+    {
+        unwrap(Tensor::try_stack(tensors, axis))
+    }
+
+    ///
+    /// Split into pieces of the given sizes along `axis`, the sizes must add
+    /// up to the size of the axis.
+    ///
+    pub fn try_split(&self, sizes: &[usize], axis: usize) -> Result<Vec<Tensor<T>>>
+    {
+        let dims = self.shape().dims();
+        if axis >= dims.len() || sizes.iter().sum::<usize>() != dims[axis]
+        {
+            let reason = format!("could not split axis {} into {:?}", axis, sizes);
+            return Err(RuneError::invalid("split", dims, reason));
+        }
+
+        let mut start = 0;
+        Ok(sizes.iter()
+            .map(|&size|
+            {
+                let mut slices = vec![Slice::from(..); axis + 1];
+                slices[axis] = Slice::from(start..start + size);
+                start += size;
+                self.slice(&slices)
+            })
+            .collect())
+    }
+
+    pub fn split(&self, sizes: &[usize], axis: usize) -> Vec<Tensor<T>>
+    {
+        unwrap(self.try_split(sizes, axis))
+    }
+
+    ///
+    /// Split into `n` pieces of equal size along `axis`. If the axis does 
+    /// not divide evenly the last piece is smaller, and fewer than `n` 
+    /// pieces are returned when the axis is too small to fill them all.
+    ///
+    /// # Example
+    ///
+    /// let t = Tensor::<f32>::ones(&[10, 4]);
+    /// let chunks = t.chunk(3, 0);
+    ///
+    /// >>> chunks.len() = 3, with 4, 4 and 2 rows
+    ///
+    pub fn try_chunk(&self, n: usize, axis: usize) -> Result<Vec<Tensor<T>>>
+    {
+        let dims = self.shape().dims();
+        if n == 0 || axis >= dims.len()
+        {
+            let reason = format!("could not chunk axis {} into {} pieces", axis, n);
+            return Err(RuneError::invalid("chunk", dims, reason));
+        }
+
+        let size = dims[axis].div_ceil(n).max(1);
+        let mut sizes = vec![size; dims[axis] / size];
+        if !dims[axis].is_multiple_of(size)
+        {
+            sizes.push(dims[axis] % size);
+        }
+        self.try_split(&sizes, axis)
+    }
+
+    pub fn chunk(&self, n: usize, axis: usize) -> Vec<Tensor<T>>
+    {
+        unwrap(self.try_chunk(n, axis))
+    }
+}
+
+///
+/// FUNCTIONAL UNIT TESTING
+///
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::autograd::gradcheck;
+
+    use ndarray::ArrayD;
+    use ndarray::IxDyn;
+
+    fn arange(dims: &[usize]) -> Tensor<f64>
+    {
+        let len = dims.iter().product::<usize>();
+        let data = (0..len).map(|i| i as f64).collect();
+        let mut t = Tensor::new(ArrayD::from_shape_vec(IxDyn(dims), data).unwrap());
+        t.set_requires_grad(true);
+        t
+    }
+
+    fn weighted(t: Tensor<f64>) -> Tensor<f64>
+    {
+        let weight = arange(t.shape().dims()).data().mapv(|v| v + 1.0);
+        t.mul(&Tensor::new(weight))
+    }
+
+    #[test]
+    fn cat()
+    {
+        let a = arange(&[2, 1]);
+        let b = arange(&[2, 2]);
+        let c = Tensor::cat(&[a.clone(), b.clone()], 1);
+
+        assert_eq!(c.data().as_slice().unwrap(), &[0.0, 0.0, 1.0, 1.0, 2.0, 3.0]);
+
+        weighted(c).sum_all().backward();
+        assert_eq!(a.grad().as_slice().unwrap(), &[1.0, 4.0]);
+        assert_eq!(b.grad().as_slice().unwrap(), &[2.0, 3.0, 5.0, 6.0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn cat_mismatch()
+    {
+        let _c = Tensor::cat(&[Tensor::<f32>::ones(&[2, 1]), Tensor::ones(&[3, 2])], 1);
+    }
+
+    #[test]
+    fn fallible()
+    {
+        let a = Tensor::<f32>::ones(&[2, 1]);
+        let b = Tensor::<f32>::ones(&[3, 2]);
+
+        assert_eq!(
+            Tensor::try_cat(&[a.clone(), b.clone()], 1).err(),
+            Some(RuneError::shapes("cat", &[&[2, 1], &[3, 2]])),
+        );
+        assert!(Tensor::<f32>::try_cat(&[], 0).is_err());
+        assert!(Tensor::try_stack(&[a.clone(), b.clone()], 0).is_err());
+        assert!(b.try_split(&[1, 1], 0).is_err());
+        assert!(b.try_chunk(0, 1).is_err());
+        assert_eq!(b.try_chunk(2, 1).map(|c| c.len()), Ok(2));
+    }
+
+    #[test]
+    fn stack()
+    {
+        let a = Tensor::<f32>::ones(&[4, 3]);
+        let b = Tensor::<f32>::zeros(&[4, 3]);
+
+        assert_eq!(*Tensor::stack(&[a.clone(), b.clone()], 0).shape().dims(), vec![2, 4, 3]);
+        assert_eq!(*Tensor::stack(&[a, b], 2).shape().dims(), vec![4, 3, 2]);
+    }
+
+    #[test]
+    fn split_and_chunk()
+    {
+        let t = arange(&[10, 2]);
+        let pieces = t.split(&[3, 7], 0);
+        assert_eq!(*pieces[0].shape().dims(), vec![3, 2]);
+        assert_eq!(pieces[1].data()[[0, 0]], 6.0);
+
+        let chunks = t.chunk(3, 0);
+        let rows: Vec<usize> = chunks.iter().map(|c| c.shape().dims()[0]).collect();
+        assert_eq!(rows, vec![4, 4, 2]);
+
+        let chunks = t.chunk(3, 1);
+        let cols: Vec<usize> = chunks.iter().map(|c| c.shape().dims()[1]).collect();
+        assert_eq!(cols, vec![1, 1]);
+
+        let heads = Tensor::cat(&t.chunk(5, 0), 0);
+        assert_eq!(*heads.data(), *t.data());
+    }
+
+    #[test]
+    fn gradients()
+    {
+        let inputs = [arange(&[2, 3]), arange(&[2, 2])];
+        assert!(gradcheck(|t| weighted(Tensor::cat(t, 1)), &inputs, 1e-3, 1e-6));
+
+        let inputs = [arange(&[2, 3]), arange(&[2, 3])];
+        assert!(gradcheck(|t| weighted(Tensor::stack(t, 1)), &inputs, 1e-3, 1e-6));
+
+        let x = [arange(&[5, 3])];
+        let join = |pieces: Vec<Tensor<f64>>| Tensor::cat(&[pieces[2].clone(), pieces[0].clone()], 0);
+        assert!(gradcheck(|t| weighted(join(t[0].split(&[1, 2, 2], 0))), &x, 1e-3, 1e-6));
+        assert!(gradcheck(|t| weighted(join(t[0].chunk(3, 0))), &x, 1e-3, 1e-6));
+    }
+}
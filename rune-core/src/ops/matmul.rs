@@ -0,0 +1,324 @@
+//
+// MIT License
+// 
+// Copyright (c) 2023 Wilhelm Ågren
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// 
+// File created: 2026-10-17
+// Last updated: 2026-10-17
+//
+
+use crate::datatype::DataType;
+use crate::error::unwrap;
+use crate::error::Result;
+use crate::error::RuneError;
+use crate::ops::gemm::gemm;
+use crate::shape::Shape;
+use crate::tensor::Tensor;
+use crate::utils::reduce_to_shape;
+
+use ndarray::ArrayD;
+use ndarray::ArrayViewD;
+use ndarray::IxDyn;
+
+///
+/// Matrix product with NumPy `matmul` semantics.
+///
+/// - If both tensors are 1-D the result is their dot product, a 0-D tensor.
+/// - A 1-D lhs is promoted to a (1, k) matrix and a 1-D rhs to a (k, 1) 
+///   matrix, the promoted dim is removed from the result afterwards.
+/// - Otherwise the last two dims are the matrices and all leading dims are
+///   batch dims, which are broadcast against each other, e.g.
+///   [B, H, T, D] @ [B, H, D, T] -> [B, H, T, T] and
+///   [2, 1, 3, 4] @ [5, 4, 2] -> [2, 5, 3, 2].
+///
+impl<T: DataType> Tensor<T>
+{
+    pub fn try_matmul(&self, other: &Tensor<T>) -> Result<Tensor<T>>
+    {
+        let lhs_dims = self.shape().dims();
+        let rhs_dims = other.shape().dims();
+        let spec = match MatmulSpec::new(lhs_dims, rhs_dims)
+        {
+            Some(spec) => spec,
+            None => return Err(RuneError::shapes("matmul", &[lhs_dims, rhs_dims])),
+        };
+
+        let lhs = self.data();
+        let rhs = other.data();
+        let lhs = lhs.view().into_shape(spec.lhs.dims().as_slice()).map_err(|_| RuneError::NonContiguous { op: "matmul" })?;
+        let rhs = rhs.view().into_shape(spec.rhs.dims().as_slice()).map_err(|_| RuneError::NonContiguous { op: "matmul" })?;
+        let data = batched_matmul(&lhs, &rhs)
+            .into_shape(spec.out.dims().as_slice())
+            .unwrap();
+
+        Ok(Tensor::from_op(data, vec![self.clone(), other.clone()], ||
+        {
+            let lhs = self.clone();
+            let rhs = other.clone();
+            Box::new(move |grad|
+            {
+                let out_dims = IxDyn(&spec.batched_out);
+                let grad = grad.view().into_shape(out_dims).unwrap();
+                let a = lhs.data();
+                let b = rhs.data();
+                let a = a.view().into_shape(spec.lhs.dims().as_slice()).unwrap();
+                let b = b.view().into_shape(spec.rhs.dims().as_slice()).unwrap();
+
+                // da = g @ b^T and db = a^T @ g, summed over broadcast batch dims.
+                let d_lhs = batched_matmul(&grad, &transpose(&b));
+                let d_rhs = batched_matmul(&transpose(&a), &grad);
+                let d_lhs = reduce_to_shape(&d_lhs, &spec.lhs);
+                let d_rhs = reduce_to_shape(&d_rhs, &spec.rhs);
+                vec![
+                    d_lhs.into_shape(lhs.shape().dims().as_slice()).unwrap(),
+                    d_rhs.into_shape(rhs.shape().dims().as_slice()).unwrap(),
+                ]
+            })
+        }))
+    }
+
+    pub fn matmul(&self, other: &Tensor<T>) -> Tensor<T>
+    {
+        unwrap(self.try_matmul(other))
+    }
+}
+
+///
+/// The shapes involved in a matmul once 1-D operands have been promoted to
+/// matrices. `batched_out` is the output shape before the promoted dims are
+/// removed again, which is the shape that the gradient is computed in.
+///
+struct MatmulSpec
+{
+    lhs: Shape,
+    rhs: Shape,
+    out: Shape,
+    batched_out: Vec<usize>,
+}
+
+impl MatmulSpec
+{
+    fn new(lhs_dims: &[usize], rhs_dims: &[usize]) -> Option<Self>
+    {
+        if lhs_dims.is_empty() || rhs_dims.is_empty()
+        {
+            return None;
+        }
+
+        let lhs = match lhs_dims.len()
+        {
+            1 => vec![1, lhs_dims[0]],
+            _ => lhs_dims.to_vec(),
+        };
+        let rhs = match rhs_dims.len()
+        {
+            1 => vec![rhs_dims[0], 1],
+            _ => rhs_dims.to_vec(),
+        };
+
+        let (m, k) = (lhs[lhs.len() - 2], lhs[lhs.len() - 1]);
+        let (k_rhs, n) = (rhs[rhs.len() - 2], rhs[rhs.len() - 1]);
+        if k != k_rhs
+        {
+            return None;
+        }
+
+        let lhs_batch = Shape::new(&lhs[..lhs.len() - 2]);
+        let rhs_batch = Shape::new(&rhs[..rhs.len() - 2]);
+        let mut batched_out = lhs_batch.broadcast(&rhs_batch)?.dims().to_vec();
+        let mut out = batched_out.clone();
+        batched_out.extend([m, n]);
+
+        if lhs_dims.len() > 1
+        {
+            out.push(m);
+        }
+        if rhs_dims.len() > 1
+        {
+            out.push(n);
+        }
+
+        Some(MatmulSpec
+        {
+            lhs: Shape::new(&lhs),
+            rhs: Shape::new(&rhs),
+            out: Shape::new(&out),
+            batched_out,
+        })
+    }
+}
+
+fn transpose<'a, T>(a: &ArrayViewD<'a, T>) -> ArrayViewD<'a, T>
+{
+    let mut t = a.clone();
+    let ndim = t.ndim();
+    t.swap_axes(ndim - 2, ndim - 1);
+    t
+}
+
+///
+/// Multiply two arrays of at least two dims, broadcasting the batch dims.
+/// Every matrix in the batch is computed by `gemm`.
+///
+fn batched_matmul<T: DataType>(a: &ArrayViewD<T>, b: &ArrayViewD<T>) -> ArrayD<T>
+{
+    let (a_ndim, b_ndim) = (a.ndim(), b.ndim());
+    let (m, k) = (a.shape()[a_ndim - 2], a.shape()[a_ndim - 1]);
+    let n = b.shape()[b_ndim - 1];
+
+    let a_batch = Shape::new(&a.shape()[..a_ndim - 2]);
+    let b_batch = Shape::new(&b.shape()[..b_ndim - 2]);
+    let batch = a_batch.broadcast(&b_batch).unwrap();
+    let num_batches: usize = batch.dims().iter().product();
+
+    let mut a_dims = batch.dims().clone();
+    a_dims.extend([m, k]);
+    let mut b_dims = batch.dims().clone();
+    b_dims.extend([k, n]);
+    let mut out_dims = batch.dims().clone();
+    out_dims.extend([m, n]);
+
+    let a = a.broadcast(IxDyn(&a_dims)).unwrap();
+    let b = b.broadcast(IxDyn(&b_dims)).unwrap();
+    let a = a.as_standard_layout();
+    let b = b.as_standard_layout();
+    let a = a.as_slice().unwrap();
+    let b = b.as_slice().unwrap();
+
+    let mut out = vec![T::zero(); num_batches * m * n];
+    if m * n > 0
+    {
+        for (i, c) in out.chunks_mut(m * n).enumerate()
+        {
+            gemm(m, k, n, &a[i * m * k..(i + 1) * m * k], &b[i * k * n..(i + 1) * k * n], c);
+        }
+    }
+
+    ArrayD::from_shape_vec(IxDyn(&out_dims), out).unwrap()
+}
+
+///
+/// FUNCTIONAL UNIT TESTING
+///
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn arange(dims: &[usize]) -> Tensor<f64>
+    {
+        let len = dims.iter().product::<usize>();
+        let data = (0..len).map(|i| (i % 7) as f64 - 2.0).collect();
+        let mut t = Tensor::new(ArrayD::from_shape_vec(IxDyn(dims), data).unwrap());
+        t.set_requires_grad(true);
+        t
+    }
+
+    fn grads(a: &Tensor<f64>, b: &Tensor<f64>) -> (Tensor<f64>, Vec<ArrayD<f64>>)
+    {
+        let c = a.matmul(b);
+        let ones = ArrayD::ones(c.shape().dims().as_slice());
+        let grads = c.grad_fn().unwrap()(&ones);
+        (c, grads)
+    }
+
+    #[test]
+    fn shapes()
+    {
+        let cases: [(&[usize], &[usize], &[usize]); 7] = [
+            (&[3], &[3], &[]),
+            (&[3], &[3, 4], &[4]),
+            (&[2, 3], &[3], &[2]),
+            (&[2, 3], &[3, 4], &[2, 4]),
+            (&[5, 2, 3], &[3], &[5, 2]),
+            (&[2, 1, 3, 4], &[5, 4, 2], &[2, 5, 3, 2]),
+            (&[2, 4, 8, 16], &[2, 4, 16, 8], &[2, 4, 8, 8]),
+        ];
+
+        for (lhs, rhs, out) in cases
+        {
+            let c = Tensor::<f32>::ones(lhs).matmul(&Tensor::ones(rhs));
+            assert_eq!(c.shape().dims().as_slice(), out);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn mismatch()
+    {
+        let _c = Tensor::<f32>::ones(&[2, 3, 4]).matmul(&Tensor::ones(&[3, 3, 2]));
+    }
+
+    #[test]
+    fn vectors()
+    {
+        let a = arange(&[3]);
+        let b = arange(&[3]);
+        let (c, g) = grads(&a, &b);
+
+        assert_eq!(c.data()[[]], 4.0 + 1.0);
+        assert_eq!(g[0], *b.data());
+        assert_eq!(g[1], *a.data());
+
+        let m = arange(&[3, 2]);
+        let (c, g) = grads(&a, &m);
+        assert_eq!(c.data().as_slice().unwrap(), &[4.0, 1.0]);
+        assert_eq!(g[0].as_slice().unwrap(), &[-3.0, 1.0, 5.0]);
+        assert_eq!(g[1].shape(), &[3, 2]);
+    }
+
+    #[test]
+    fn batched()
+    {
+        let a = arange(&[2, 1, 3, 4]);
+        let b = arange(&[5, 4, 2]);
+        let (c, g) = grads(&a, &b);
+
+        let a_data = a.data();
+        let b_data = b.data();
+        for (i, j, r, s) in ndarray::indices((2, 5, 3, 2))
+        {
+            let expected: f64 = (0..4).map(|p| a_data[[i, 0, r, p]] * b_data[[j, p, s]]).sum();
+            assert_eq!(c.data()[[i, j, r, s]], expected);
+        }
+
+        // With g = ones, da[i, 0, r, p] = sum_j sum_s b[j, p, s]
+        // and db[j, p, s] = sum_i sum_r a[i, 0, r, p].
+        assert_eq!(g[0].shape(), &[2, 1, 3, 4]);
+        assert_eq!(g[1].shape(), &[5, 4, 2]);
+        for (i, r, p) in ndarray::indices((2, 3, 4))
+        {
+            let expected: f64 = ndarray::indices((5, 2))
+                .into_iter()
+                .map(|(j, s)| b_data[[j, p, s]])
+                .sum();
+            assert_eq!(g[0][[i, 0, r, p]], expected);
+        }
+        for (j, p, s) in ndarray::indices((5, 4, 2))
+        {
+            let expected: f64 = ndarray::indices((2, 3))
+                .into_iter()
+                .map(|(i, r)| a_data[[i, 0, r, p]])
+                .sum();
+            assert_eq!(g[1][[j, p, s]], expected);
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//
+// MIT License
+// 
+// Copyright (c) 2023 Wilhelm Ågren
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// 
+// File created: 2026-10-17
+// Last updated: 2026-10-17
+//
+
+use crate::datatype::DataType;
+use crate::error::unwrap;
+use crate::error::Result;
+use crate::error::RuneError;
+use crate::tensor::Tensor;
+
+use ndarray::ArrayD;
+use ndarray::Axis;
+
+///
+/// The normalized tensor, and the mean and biased variance that it was
+/// normalized with, which keep the reduced axes with size 1.
+///
+pub(crate) type Normalized<T> = (Tensor<T>, ArrayD<T>, ArrayD<T>);
+
+impl<T: DataType> Tensor<T>
+{
+    ///
+    /// (x - mean) / sqrt(var + eps), where the mean and the biased variance
+    /// are taken over `axes`. This is the shared core of all normalization
+    /// layers, which only differ in the axes that they normalize over.
+    ///
+    /// # Example
+    ///
+    /// let t = Tensor::<f32>::new(array![[1.0, 3.0], [2.0, 6.0]].into_dyn());
+    ///
+    /// >>> t.normalize(&[1], 0.0).data() = [[-1.0, 1.0], [-1.0, 1.0]]
+    ///
+    pub fn try_normalize(&self, axes: &[usize], eps: T) -> Result<Tensor<T>>
+    {
+        Ok(self.normalize_with_stats("normalize", axes, eps)?.0)
+    }
+
+    pub fn normalize(&self, axes: &[usize], eps: T) -> Tensor<T>
+    {
+        unwrap(self.try_normalize(axes, eps))
+    }
+
+    pub(crate) fn normalize_with_stats(&self, op: &'static str, axes: &[usize], eps: T) -> Result<Normalized<T>>
+    {
+        self.shape().reduce(axes, true).map_err(|e| match e
+        {
+            RuneError::InvalidShape { shape, reason, .. } =>
+                RuneError::InvalidShape { op, shape, reason },
+            e => e,
+        })?;
+
+        let x = self.data();
+        let n = T::from(axes.iter().map(|&a| x.shape()[a]).product::<usize>()).unwrap();
+        let mean = keep_sum(&x, axes).mapv(|s| s / n);
+        let centered = &*x - &mean;
+        let var = keep_sum(&centered.mapv(|c| c * c), axes).mapv(|s| s / n);
+        let inv_std = var.mapv(|v| T::one() / (v + eps).sqrt());
+        let normalized = &centered * &inv_std;
+        drop(x);
+
+        let output = normalized.clone();
+        let axes = axes.to_vec();
+        let tensor = Tensor::from_op(normalized, vec![self.clone()], ||
+        {
+            // dx = (g - mean(g) - y * mean(g * y)) / sqrt(var + eps) over the axes.
+            Box::new(move |grad|
+            {
+                let mean_grad = keep_sum(grad, &axes).mapv(|s| s / n);
+                let mean_dot = keep_sum(&(grad * &output), &axes).mapv(|s| s / n);
+                vec![&(&(grad - &mean_grad) - &(&output * &mean_dot)) * &inv_std]
+            })
+        });
+        Ok((tensor, mean, var))
+    }
+}
+
+///
+/// Sum over `axes` while keeping them with size 1.
+///
+fn keep_sum<T: DataType>(x: &ArrayD<T>, axes: &[usize]) -> ArrayD<T>
+{
+    axes.iter().fold(x.clone(), |s, &a| s.sum_axis(Axis(a)).insert_axis(Axis(a)))
+}
+
+///
+/// FUNCTIONAL UNIT TESTING
+///
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::autograd::gradcheck;
+    use crate::random::Generator;
+
+    fn random(dims: &[usize], seed: u64) -> Tensor<f64>
+    {
+        let mut t = Tensor::uniform_with(dims, -1.0, 1.0, &mut Generator::new(seed));
+        t.set_requires_grad(true);
+        t
+    }
+
+    #[test]
+    fn statistics()
+    {
+        let t = random(&[4, 3, 5], 0);
+        for axes in [vec![0], vec![0, 2], vec![1, 2]]
+        {
+            let (y, mean, var) = t.normalize_with_stats("normalize", &axes, 0.0).unwrap();
+            let y = y.data();
+            assert_eq!(mean.shape(), var.shape());
+            assert!(keep_sum(&y, &axes).iter().all(|s| s.abs() < 1e-12));
+
+            let n = axes.iter().map(|&a| t.shape().dims()[a]).product::<usize>() as f64;
+            assert!(keep_sum(&y.mapv(|v| v * v), &axes).iter().all(|s| (s / n - 1.0).abs() < 1e-12));
+        }
+
+        let (_, mean, var) = t.normalize_with_stats("normalize", &[0, 1, 2], 1e-5).unwrap();
+        let data = t.data();
+        assert!((mean[[0, 0, 0]] - data.mean().unwrap()).abs() < 1e-12);
+        assert!((var[[0, 0, 0]] - data.mapv(|v| v * v).mean().unwrap() + mean[[0, 0, 0]].powi(2)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn invalid()
+    {
+        let t = Tensor::<f32>::ones(&[2, 3]);
+        assert!(t.try_normalize(&[2], 1e-5).is_err());
+        assert!(t.try_normalize(&[1, 1], 1e-5).is_err());
+    }
+
+    #[test]
+    fn gradients()
+    {
+        let x = [random(&[3, 4, 2], 1)];
+        let m = Tensor::new(random(&[3, 4, 2], 2).data().clone());
+
+        assert!(gradcheck(|t| t[0].normalize(&[1], 1e-5).mul(&m), &x, 1e-6, 1e-6));
+        assert!(gradcheck(|t| t[0].normalize(&[0, 2], 1e-5).mul(&m), &x, 1e-6, 1e-6));
+        assert!(gradcheck(|t| t[0].normalize(&[2, 1, 0], 1e-2).mul(&m), &x, 1e-6, 1e-6));
+    }
+}
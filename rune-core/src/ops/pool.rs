@@ -0,0 +1,689 @@
+//
+// MIT License
+// 
+// Copyright (c) 2023 Wilhelm Ågren
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// 
+// File created: 2026-10-17
+// Last updated: 2026-10-17
+//
+
+use crate::datatype::DataType;
+use crate::error::unwrap;
+use crate::error::Result;
+use crate::error::RuneError;
+use crate::tensor::Tensor;
+use crate::utils::contiguous;
+
+use ndarray::indices;
+use ndarray::ArrayD;
+use ndarray::IxDyn;
+
+const MAX_POOL_OPS: [&str; 3] = ["max_pool1d", "max_pool2d", "max_pool3d"];
+const AVG_POOL_OPS: [&str; 3] = ["avg_pool1d", "avg_pool2d", "avg_pool3d"];
+const ADAPTIVE_AVG_POOL_OPS: [&str; 3] = ["adaptive_avg_pool1d", "adaptive_avg_pool2d", "adaptive_avg_pool3d"];
+const ADAPTIVE_MAX_POOL_OPS: [&str; 3] = ["adaptive_max_pool1d", "adaptive_max_pool2d", "adaptive_max_pool3d"];
+
+///
+/// The input positions that every output position of a pool reduces over,
+/// as flat offsets into a single channel of the input. `divisors` holds the
+/// window sizes that average pooling divides by, which count the padding
+/// but not the parts of a window that `ceil_mode` lets hang past it.
+///
+struct Regions
+{
+    output: Vec<usize>,
+    image: usize,
+    members: Vec<Vec<usize>>,
+    divisors: Vec<usize>,
+}
+
+impl Regions
+{
+    fn sliding(
+        op: &'static str,
+        input: &[usize],
+        kernel: &[usize],
+        stride: &[usize],
+        padding: &[usize],
+        ceil_mode: bool,
+    ) -> Result<Self>
+    {
+        let mut output = Vec::with_capacity(input.len());
+        for (d, &size) in input.iter().enumerate()
+        {
+            if kernel[d] == 0 || stride[d] == 0
+            {
+                let reason = "kernel and stride must be positive".to_string();
+                return Err(RuneError::invalid(op, input, reason));
+            }
+            if size == 0
+            {
+                return Err(RuneError::invalid(op, input, "spatial dims must be positive".to_string()));
+            }
+            if padding[d] > kernel[d] / 2
+            {
+                let reason = format!("padding {} must be at most half of the kernel {}", padding[d], kernel[d]);
+                return Err(RuneError::invalid(op, input, reason));
+            }
+            if size + 2 * padding[d] < kernel[d]
+            {
+                let reason = format!("kernel {} does not fit in the padded input", kernel[d]);
+                return Err(RuneError::invalid(op, input, reason));
+            }
+
+            // The last window has to start inside the input or its left padding.
+            let span = size + 2 * padding[d] - kernel[d];
+            let mut out = match ceil_mode
+            {
+                true => span.div_ceil(stride[d]) + 1,
+                false => span / stride[d] + 1,
+            };
+            if ceil_mode && (out - 1) * stride[d] >= size + padding[d]
+            {
+                out -= 1;
+            }
+            output.push(out);
+        }
+
+        let mut members = Vec::new();
+        let mut divisors = Vec::new();
+        for o in indices(IxDyn(&output))
+        {
+            let mut region = Vec::new();
+            let mut divisor = 0;
+            for k in indices(IxDyn(kernel))
+            {
+                let mut offset = Some(0);
+                let mut inside_padding = true;
+                for (d, &size) in input.iter().enumerate()
+                {
+                    let pos = o[d] * stride[d] + k[d];
+                    inside_padding &= pos < size + 2 * padding[d];
+                    offset = match (offset, pos >= padding[d] && pos < size + padding[d])
+                    {
+                        (Some(offset), true) => Some(offset * size + pos - padding[d]),
+                        _ => None,
+                    };
+                }
+                region.extend(offset);
+                divisor += inside_padding as usize;
+            }
+            members.push(region);
+            divisors.push(divisor);
+        }
+
+        Ok(Regions { output, image: input.iter().product(), members, divisors })
+    }
+
+    ///
+    /// Output position i along a dim covers the input range from
+    /// floor(i * input / output) to ceil((i + 1) * input / output).
+    ///
+    fn adaptive(op: &'static str, input: &[usize], output: &[usize]) -> Result<Self>
+    {
+        if input.contains(&0) || output.contains(&0)
+        {
+            let reason = format!("can not pool to an output of {:?}", output);
+            return Err(RuneError::invalid(op, input, reason));
+        }
+
+        let mut members = Vec::new();
+        let mut divisors = Vec::new();
+        for o in indices(IxDyn(output))
+        {
+            let ranges: Vec<(usize, usize)> = input.iter().enumerate()
+                .map(|(d, &size)| (o[d] * size / output[d], ((o[d] + 1) * size).div_ceil(output[d])))
+                .collect();
+            let extent: Vec<usize> = ranges.iter().map(|(start, end)| end - start).collect();
+
+            let mut region = Vec::new();
+            for k in indices(IxDyn(&extent))
+            {
+                let offset = input.iter().enumerate().fold(0, |offset, (d, &size)| offset * size + ranges[d].0 + k[d]);
+                region.push(offset);
+            }
+            divisors.push(region.len());
+            members.push(region);
+        }
+
+        Ok(Regions { output: output.to_vec(), image: input.iter().product(), members, divisors })
+    }
+}
+
+///
+/// Pooling over the last N dims of inputs of [batch, channels, *spatial],
+/// or without the batch dim. Sliding pools take a kernel, a stride and an
+/// implicit padding of at most half the kernel, where `ceil_mode` rounds
+/// the output size up so that the last partial window is kept. Max pooling
+/// never picks the padding and average pooling counts it in the divisor.
+///
+/// Adaptive pools choose their windows to produce an output of a given
+/// size, where pooling to an output of ones is global pooling.
+///
+impl<T: DataType> Tensor<T>
+{
+    ///
+    /// Max pool a sequence of [batch, channels, length].
+    ///
+    /// # Example
+    ///
+    /// let t = Tensor::<f32>::new(array![[[1.0, 3.0, 2.0, 5.0, 4.0]]].into_dyn());
+    ///
+    /// >>> t.max_pool1d([2], [2], [0], true).data() = [[[3.0, 5.0, 4.0]]]
+    ///
+    pub fn try_max_pool1d(&self, kernel: [usize; 1], stride: [usize; 1], padding: [usize; 1], ceil_mode: bool) -> Result<Tensor<T>>
+    {
+        Ok(self.try_max_pool1d_with_indices(kernel, stride, padding, ceil_mode)?.0)
+    }
+
+    pub fn max_pool1d(&self, kernel: [usize; 1], stride: [usize; 1], padding: [usize; 1], ceil_mode: bool) -> Tensor<T>
+    {
+        unwrap(self.try_max_pool1d(kernel, stride, padding, ceil_mode))
+    }
+
+    ///
+    /// Max pool a sequence and also return the flat index into its length
+    /// that every max was taken from, for `max_unpool1d`.
+    ///
+    pub fn try_max_pool1d_with_indices(
+        &self,
+        kernel: [usize; 1],
+        stride: [usize; 1],
+        padding: [usize; 1],
+        ceil_mode: bool,
+    ) -> Result<(Tensor<T>, ArrayD<usize>)>
+    {
+        self.try_max_pool_with_indices(kernel, stride, padding, ceil_mode)
+    }
+
+    pub fn max_pool1d_with_indices(
+        &self,
+        kernel: [usize; 1],
+        stride: [usize; 1],
+        padding: [usize; 1],
+        ceil_mode: bool,
+    ) -> (Tensor<T>, ArrayD<usize>)
+    {
+        unwrap(self.try_max_pool1d_with_indices(kernel, stride, padding, ceil_mode))
+    }
+
+    ///
+    /// Max pool an image of [batch, channels, height, width].
+    ///
+    /// # Example
+    ///
+    /// let t = Tensor::<f32>::ones(&[8, 16, 32, 32]);
+    ///
+    /// >>> t.max_pool2d([3, 3], [2, 2], [1, 1], false).shape() = Shape { dims: [8, 16, 16, 16] }
+    ///
+    pub fn try_max_pool2d(&self, kernel: [usize; 2], stride: [usize; 2], padding: [usize; 2], ceil_mode: bool) -> Result<Tensor<T>>
+    {
+        Ok(self.try_max_pool2d_with_indices(kernel, stride, padding, ceil_mode)?.0)
+    }
+
+    pub fn max_pool2d(&self, kernel: [usize; 2], stride: [usize; 2], padding: [usize; 2], ceil_mode: bool) -> Tensor<T>
+    {
+        unwrap(self.try_max_pool2d(kernel, stride, padding, ceil_mode))
+    }
+
+    ///
+    /// Max pool an image and also return the flat index into its height
+    /// times width that every max was taken from, for `max_unpool2d`.
+    ///
+    pub fn try_max_pool2d_with_indices(
+        &self,
+        kernel: [usize; 2],
+        stride: [usize; 2],
+        padding: [usize; 2],
+        ceil_mode: bool,
+    ) -> Result<(Tensor<T>, ArrayD<usize>)>
+    {
+        self.try_max_pool_with_indices(kernel, stride, padding, ceil_mode)
+    }
+
+    pub fn max_pool2d_with_indices(
+        &self,
+        kernel: [usize; 2],
+        stride: [usize; 2],
+        padding: [usize; 2],
+        ceil_mode: bool,
+    ) -> (Tensor<T>, ArrayD<usize>)
+    {
+        unwrap(self.try_max_pool2d_with_indices(kernel, stride, padding, ceil_mode))
+    }
+
+    pub fn try_avg_pool1d(&self, kernel: [usize; 1], stride: [usize; 1], padding: [usize; 1], ceil_mode: bool) -> Result<Tensor<T>>
+    {
+        self.try_avg_pool(kernel, stride, padding, ceil_mode)
+    }
+
+    pub fn avg_pool1d(&self, kernel: [usize; 1], stride: [usize; 1], padding: [usize; 1], ceil_mode: bool) -> Tensor<T>
+    {
+        unwrap(self.try_avg_pool1d(kernel, stride, padding, ceil_mode))
+    }
+
+    pub fn try_avg_pool2d(&self, kernel: [usize; 2], stride: [usize; 2], padding: [usize; 2], ceil_mode: bool) -> Result<Tensor<T>>
+    {
+        self.try_avg_pool(kernel, stride, padding, ceil_mode)
+    }
+
+    pub fn avg_pool2d(&self, kernel: [usize; 2], stride: [usize; 2], padding: [usize; 2], ceil_mode: bool) -> Tensor<T>
+    {
+        unwrap(self.try_avg_pool2d(kernel, stride, padding, ceil_mode))
+    }
+
+    pub fn try_adaptive_avg_pool1d(&self, output: [usize; 1]) -> Result<Tensor<T>>
+    {
+        self.try_adaptive_avg_pool(output)
+    }
+
+    pub fn adaptive_avg_pool1d(&self, output: [usize; 1]) -> Tensor<T>
+    {
+        unwrap(self.try_adaptive_avg_pool1d(output))
+    }
+
+    ///
+    /// Average pool an image to [output_height, output_width].
+    ///
+    /// # Example
+    ///
+    /// let t = Tensor::<f32>::ones(&[8, 512, 7, 7]);
+    ///
+    /// >>> t.adaptive_avg_pool2d([1, 1]).shape() = Shape { dims: [8, 512, 1, 1] }
+    ///
+    pub fn try_adaptive_avg_pool2d(&self, output: [usize; 2]) -> Result<Tensor<T>>
+    {
+        self.try_adaptive_avg_pool(output)
+    }
+
+    pub fn adaptive_avg_pool2d(&self, output: [usize; 2]) -> Tensor<T>
+    {
+        unwrap(self.try_adaptive_avg_pool2d(output))
+    }
+
+    pub fn try_adaptive_max_pool1d(&self, output: [usize; 1]) -> Result<Tensor<T>>
+    {
+        self.try_adaptive_max_pool(output)
+    }
+
+    pub fn adaptive_max_pool1d(&self, output: [usize; 1]) -> Tensor<T>
+    {
+        unwrap(self.try_adaptive_max_pool1d(output))
+    }
+
+    pub fn try_adaptive_max_pool2d(&self, output: [usize; 2]) -> Result<Tensor<T>>
+    {
+        self.try_adaptive_max_pool(output)
+    }
+
+    pub fn adaptive_max_pool2d(&self, output: [usize; 2]) -> Tensor<T>
+    {
+        unwrap(self.try_adaptive_max_pool2d(output))
+    }
+
+    ///
+    /// Scatter the values of a max pooled sequence back to the positions in
+    /// `indices` of a zero sequence of length `output`, the pre-pool length.
+    ///
+    pub fn try_max_unpool1d(&self, indices: &ArrayD<usize>, output: [usize; 1]) -> Result<Tensor<T>>
+    {
+        self.unpool("max_unpool1d", indices, &output)
+    }
+
+    pub fn max_unpool1d(&self, indices: &ArrayD<usize>, output: [usize; 1]) -> Tensor<T>
+    {
+        unwrap(self.try_max_unpool1d(indices, output))
+    }
+
+    ///
+    /// Scatter the values of a max pooled image back to the positions in
+    /// `indices` of a zero image of `output`, the pre-pool height and width.
+    /// Every position that was not a max stays zero.
+    ///
+    /// # Example
+    ///
+    /// let t = Tensor::<f32>::normal(&[1, 1, 4, 4], 0.0, 1.0);
+    /// let (pooled, indices) = t.max_pool2d_with_indices([2, 2], [2, 2], [0, 0], false);
+    ///
+    /// >>> pooled.max_unpool2d(&indices, [4, 4]).shape() = Shape { dims: [1, 1, 4, 4] }
+    ///
+    pub fn try_max_unpool2d(&self, indices: &ArrayD<usize>, output: [usize; 2]) -> Result<Tensor<T>>
+    {
+        self.unpool("max_unpool2d", indices, &output)
+    }
+
+    pub fn max_unpool2d(&self, indices: &ArrayD<usize>, output: [usize; 2]) -> Tensor<T>
+    {
+        unwrap(self.try_max_unpool2d(indices, output))
+    }
+
+    pub(crate) fn try_max_pool_with_indices<const N: usize>(
+        &self,
+        kernel: [usize; N],
+        stride: [usize; N],
+        padding: [usize; N],
+        ceil_mode: bool,
+    ) -> Result<(Tensor<T>, ArrayD<usize>)>
+    {
+        let op = MAX_POOL_OPS[N - 1];
+        let spatial = self.spatial(op, N)?;
+        self.max_over(op, Regions::sliding(op, spatial, &kernel, &stride, &padding, ceil_mode)?)
+    }
+
+    pub(crate) fn try_avg_pool<const N: usize>(
+        &self,
+        kernel: [usize; N],
+        stride: [usize; N],
+        padding: [usize; N],
+        ceil_mode: bool,
+    ) -> Result<Tensor<T>>
+    {
+        let op = AVG_POOL_OPS[N - 1];
+        let spatial = self.spatial(op, N)?;
+        self.mean_over(op, Regions::sliding(op, spatial, &kernel, &stride, &padding, ceil_mode)?)
+    }
+
+    pub(crate) fn try_adaptive_avg_pool<const N: usize>(&self, output: [usize; N]) -> Result<Tensor<T>>
+    {
+        let op = ADAPTIVE_AVG_POOL_OPS[N - 1];
+        let spatial = self.spatial(op, N)?;
+        self.mean_over(op, Regions::adaptive(op, spatial, &output)?)
+    }
+
+    pub(crate) fn try_adaptive_max_pool<const N: usize>(&self, output: [usize; N]) -> Result<Tensor<T>>
+    {
+        let op = ADAPTIVE_MAX_POOL_OPS[N - 1];
+        let spatial = self.spatial(op, N)?;
+        Ok(self.max_over(op, Regions::adaptive(op, spatial, &output)?)?.0)
+    }
+
+    ///
+    /// The last `n` dims, which are pooled over, of an input of n + 1 or
+    /// n + 2 dims.
+    ///
+    fn spatial(&self, op: &'static str, n: usize) -> Result<&[usize]>
+    {
+        let dims = self.shape().dims();
+        if dims.len() != n + 1 && dims.len() != n + 2
+        {
+            let reason = format!("expected an input of {} or {} dims", n + 1, n + 2);
+            return Err(RuneError::invalid(op, dims, reason));
+        }
+        Ok(&dims[dims.len() - n..])
+    }
+
+    fn pooled_dims(&self, output: &[usize]) -> IxDyn
+    {
+        let dims = self.shape().dims();
+        let mut pooled = dims[..dims.len() - output.len()].to_vec();
+        pooled.extend(output);
+        IxDyn(&pooled)
+    }
+
+    fn max_over(&self, op: &'static str, regions: Regions) -> Result<(Tensor<T>, ArrayD<usize>)>
+    {
+        let x = self.data();
+        let mut values = Vec::with_capacity(x.len() / regions.image * regions.members.len());
+        let mut argmax = Vec::with_capacity(values.capacity());
+        for plane in contiguous(op, &x)?.chunks(regions.image)
+        {
+            for region in regions.members.iter()
+            {
+                let best = region.iter().fold(region[0], |best, &o|
+                {
+                    match plane[o] > plane[best] || plane[o].is_nan()
+                    {
+                        true => o,
+                        false => best,
+                    }
+                });
+                values.push(plane[best]);
+                argmax.push(best);
+            }
+        }
+
+        let dims = self.pooled_dims(&regions.output);
+        let data = ArrayD::from_shape_vec(dims.clone(), values).unwrap();
+        let indices = ArrayD::from_shape_vec(dims, argmax).unwrap();
+        let (shape, image, positions) = (x.raw_dim(), regions.image, regions.members.len());
+        let argmax = indices.clone();
+        Ok((Tensor::from_op(data, vec![self.clone()], ||
+        {
+            // The gradient of every output only flows to its max.
+            Box::new(move |grad|
+            {
+                let mut dx = ArrayD::zeros(shape.clone());
+                let dxs = dx.as_slice_mut().unwrap();
+                for (i, (&g, &o)) in grad.iter().zip(argmax.iter()).enumerate()
+                {
+                    dxs[i / positions * image + o] += g;
+                }
+                vec![dx]
+            })
+        }), indices))
+    }
+
+    fn mean_over(&self, op: &'static str, regions: Regions) -> Result<Tensor<T>>
+    {
+        let x = self.data();
+        let mut values = Vec::with_capacity(x.len() / regions.image * regions.members.len());
+        for plane in contiguous(op, &x)?.chunks(regions.image)
+        {
+            for (region, &divisor) in regions.members.iter().zip(regions.divisors.iter())
+            {
+                let sum = region.iter().fold(T::zero(), |sum, &o| sum + plane[o]);
+                values.push(sum / T::from(divisor).unwrap());
+            }
+        }
+
+        let data = ArrayD::from_shape_vec(self.pooled_dims(&regions.output), values).unwrap();
+        let shape = x.raw_dim();
+        Ok(Tensor::from_op(data, vec![self.clone()], ||
+        {
+            // Every input in a window gets the gradient of the window over its divisor.
+            Box::new(move |grad|
+            {
+                let grad = grad.as_standard_layout();
+                let mut dx = ArrayD::zeros(shape.clone());
+                let planes = dx.as_slice_mut().unwrap().chunks_mut(regions.image);
+                for (plane, g) in planes.zip(grad.as_slice().unwrap().chunks(regions.members.len()))
+                {
+                    for ((region, &divisor), &g) in regions.members.iter().zip(regions.divisors.iter()).zip(g.iter())
+                    {
+                        let g = g / T::from(divisor).unwrap();
+                        region.iter().for_each(|&o| plane[o] += g);
+                    }
+                }
+                vec![dx]
+            })
+        }))
+    }
+
+    fn unpool(&self, op: &'static str, indices: &ArrayD<usize>, output: &[usize]) -> Result<Tensor<T>>
+    {
+        self.spatial(op, output.len())?;
+        if indices.shape() != self.shape().dims().as_slice()
+        {
+            return Err(RuneError::shapes(op, &[self.shape().dims(), indices.shape()]));
+        }
+        let image: usize = output.iter().product();
+        if let Some(&index) = indices.iter().find(|&&i| i >= image)
+        {
+            return Err(RuneError::IndexOutOfBounds { op, index, size: image });
+        }
+
+        let dims = self.pooled_dims(output);
+        let positions: usize = self.shape().dims()[self.shape().ndim() - output.len()..].iter().product();
+        let mut y = ArrayD::zeros(dims);
+        {
+            let ys = y.as_slice_mut().unwrap();
+            for (i, (&v, &o)) in self.data().iter().zip(indices.iter()).enumerate()
+            {
+                ys[i / positions * image + o] = v;
+            }
+        }
+
+        let indices = indices.clone();
+        let shape = self.data().raw_dim();
+        Ok(Tensor::from_op(y, vec![self.clone()], ||
+        {
+            // Every input gathers the gradient at the position it was scattered to.
+            Box::new(move |grad|
+            {
+                let grad = grad.as_standard_layout();
+                let gs = grad.as_slice().unwrap();
+                let dx = indices.iter().enumerate().map(|(i, &o)| gs[i / positions * image + o]).collect();
+                vec![ArrayD::from_shape_vec(shape.clone(), dx).unwrap()]
+            })
+        }))
+    }
+}
+
+///
+/// FUNCTIONAL UNIT TESTING
+///
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::autograd::gradcheck;
+    use crate::random::Generator;
+
+    fn tensor(dims: &[usize], values: Vec<f64>) -> Tensor<f64>
+    {
+        let mut t = Tensor::new(ArrayD::from_shape_vec(IxDyn(dims), values).unwrap());
+        t.set_requires_grad(true);
+        t
+    }
+
+    fn random(dims: &[usize], seed: u64) -> Tensor<f64>
+    {
+        let mut t = Tensor::uniform_with(dims, -1.0, 1.0, &mut Generator::new(seed));
+        t.set_requires_grad(true);
+        t
+    }
+
+    #[test]
+    fn max_pool()
+    {
+        let t = tensor(&[1, 1, 5], vec![1.0, 3.0, 2.0, 5.0, 4.0]);
+        assert_eq!(t.max_pool1d([2], [2], [0], false).data().as_slice().unwrap(), &[3.0, 5.0]);
+        assert_eq!(t.max_pool1d([2], [2], [0], true).data().as_slice().unwrap(), &[3.0, 5.0, 4.0]);
+        assert_eq!(t.max_pool1d([3], [2], [1], false).data().as_slice().unwrap(), &[3.0, 5.0, 5.0]);
+
+        let t = tensor(&[1, 4, 4], (0..16).map(|v| ((v * 7) % 16) as f64).collect());
+        let (pooled, indices) = t.max_pool2d_with_indices([2, 2], [2, 2], [0, 0], false);
+        assert_eq!(pooled.data().as_slice().unwrap(), &[12.0, 14.0, 15.0, 13.0]);
+        assert_eq!(indices.as_slice().unwrap(), &[4, 2, 9, 11]);
+    }
+
+    #[test]
+    fn ceil_mode()
+    {
+        let t = Tensor::<f32>::ones(&[2, 3, 6, 7]);
+        assert_eq!(*t.max_pool2d([3, 3], [2, 2], [0, 0], false).shape().dims(), vec![2, 3, 2, 3]);
+        assert_eq!(*t.max_pool2d([3, 3], [2, 2], [0, 0], true).shape().dims(), vec![2, 3, 3, 3]);
+
+        // The window that would start in the right padding is dropped.
+        let t = Tensor::<f32>::ones(&[1, 1, 5]);
+        assert_eq!(*t.avg_pool1d([2], [2], [1], true).shape().dims(), vec![1, 1, 3]);
+        assert_eq!(*t.avg_pool1d([4], [3], [1], true).shape().dims(), vec![1, 1, 2]);
+    }
+
+    #[test]
+    fn avg_pool()
+    {
+        let t = tensor(&[1, 1, 5], vec![1.0, 3.0, 2.0, 5.0, 4.0]);
+        assert_eq!(t.avg_pool1d([2], [2], [0], false).data().as_slice().unwrap(), &[2.0, 3.5]);
+        assert_eq!(t.avg_pool1d([2], [2], [1], false).data().as_slice().unwrap(), &[0.5, 2.5, 4.5]);
+        assert_eq!(t.avg_pool1d([2], [2], [0], true).data().as_slice().unwrap(), &[2.0, 3.5, 4.0]);
+
+        let t = Tensor::<f64>::ones(&[1, 3, 3]);
+        let pooled = t.avg_pool2d([3, 3], [1, 1], [1, 1], false);
+        assert_eq!(pooled.data()[[0, 0, 0]], 4.0 / 9.0);
+        assert_eq!(pooled.data()[[0, 1, 1]], 1.0);
+    }
+
+    #[test]
+    fn adaptive()
+    {
+        let t = tensor(&[1, 5], vec![1.0, 3.0, 2.0, 5.0, 4.0]);
+        assert_eq!(t.adaptive_avg_pool1d([2]).data().as_slice().unwrap(), &[2.0, 11.0 / 3.0]);
+        assert_eq!(t.adaptive_max_pool1d([3]).data().as_slice().unwrap(), &[3.0, 5.0, 5.0]);
+        assert_eq!(t.adaptive_max_pool1d([1]).data().as_slice().unwrap(), &[5.0]);
+
+        let t = random(&[2, 3, 7, 5], 0);
+        let global = t.adaptive_avg_pool2d([1, 1]);
+        assert_eq!(*global.shape().dims(), vec![2, 3, 1, 1]);
+        assert!((global.data()[[1, 2, 0, 0]] - t.data().slice(ndarray::s![1, 2, .., ..]).mean().unwrap()).abs() < 1e-12);
+        assert_eq!(*t.adaptive_max_pool2d([3, 7]).shape().dims(), vec![2, 3, 3, 7]);
+    }
+
+    #[test]
+    fn unpool()
+    {
+        let t = tensor(&[1, 1, 4, 4], (0..16).map(|v| ((v * 7) % 16) as f64).collect());
+        let (pooled, indices) = t.max_pool2d_with_indices([2, 2], [2, 2], [0, 0], false);
+        let unpooled = pooled.max_unpool2d(&indices, [4, 4]);
+
+        assert_eq!(*unpooled.shape().dims(), vec![1, 1, 4, 4]);
+        assert_eq!(unpooled.data().sum(), 54.0);
+        assert_eq!(unpooled.data()[[0, 0, 0, 2]], 14.0);
+        assert_eq!(unpooled.data()[[0, 0, 2, 1]], 15.0);
+        assert!(pooled.try_max_unpool2d(&indices, [2, 2]).is_err());
+        assert!(pooled.try_max_unpool1d(&indices, [16]).is_err());
+    }
+
+    #[test]
+    fn invalid()
+    {
+        let t = Tensor::<f32>::ones(&[1, 1, 4, 4]);
+        assert!(t.try_max_pool2d([3, 3], [1, 1], [2, 0], false).is_err());
+        assert!(t.try_max_pool2d([5, 3], [1, 1], [0, 0], false).is_err());
+        assert!(t.try_avg_pool2d([2, 2], [0, 1], [0, 0], false).is_err());
+        assert!(t.try_avg_pool1d([2], [1], [0], false).is_err());
+        assert!(t.try_adaptive_avg_pool2d([0, 1]).is_err());
+        assert!(Tensor::<f32>::ones(&[1, 1, 0]).try_max_pool1d([2], [1], [1], false).is_err());
+        assert!(Tensor::<f32>::ones(&[1, 1, 0]).try_avg_pool1d([2], [1], [1], false).is_err());
+    }
+
+    #[test]
+    fn gradients()
+    {
+        let x = [random(&[2, 3, 7], 1)];
+        let m = Tensor::new(random(&[2, 3, 4], 2).data().clone());
+        assert!(gradcheck(|t| t[0].max_pool1d([3], [2], [1], true).mul(&m), &x, 1e-6, 1e-6));
+        assert!(gradcheck(|t| t[0].avg_pool1d([3], [2], [1], true).mul(&m), &x, 1e-3, 1e-6));
+        assert!(gradcheck(|t| t[0].adaptive_max_pool1d([4]).mul(&m), &x, 1e-6, 1e-6));
+        assert!(gradcheck(|t| t[0].adaptive_avg_pool1d([4]).mul(&m), &x, 1e-3, 1e-6));
+
+        let x = [random(&[2, 5, 6], 3)];
+        let m = Tensor::new(random(&[2, 3, 3], 4).data().clone());
+        assert!(gradcheck(|t| t[0].max_pool2d([2, 2], [2, 2], [1, 0], false).mul(&m), &x, 1e-6, 1e-6));
+        assert!(gradcheck(|t| t[0].avg_pool2d([3, 2], [2, 2], [1, 0], false).mul(&m), &x, 1e-3, 1e-6));
+        assert!(gradcheck(|t| t[0].adaptive_avg_pool2d([3, 3]).mul(&m), &x, 1e-3, 1e-6));
+
+        let x = [random(&[1, 2, 2], 5)];
+        let indices = ArrayD::from_shape_vec(IxDyn(&[1, 2, 2]), vec![0, 3, 5, 2]).unwrap();
+        let m = Tensor::new(random(&[1, 3, 3], 6).data().clone());
+        assert!(gradcheck(|t| t[0].max_unpool2d(&indices, [3, 3]).mul(&m), &x, 1e-3, 1e-6));
+    }
+}
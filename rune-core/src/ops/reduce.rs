@@ -0,0 +1,464 @@
+//
+// MIT License
+// 
+// Copyright (c) 2023 Wilhelm Ågren
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// 
+// File created: 2026-10-17
+// Last updated: 2026-10-17
+//
+
+use crate::datatype::DataType;
+use crate::error::unwrap;
+use crate::error::Result;
+use crate::error::RuneError;
+use crate::shape::Shape;
+use crate::tensor::Tensor;
+
+use ndarray::Array2;
+use ndarray::ArrayD;
+use ndarray::IxDyn;
+
+///
+/// Decides whether `value` is better than the best value seen so far.
+///
+type Better<T> = fn(T, T) -> bool;
+
+///
+/// Bookkeeping for reducing over a set of axes. The input is permuted such
+/// that the kept axes come first and the reduced axes last, and is then
+/// viewed as a matrix of (outer, inner) where every row holds the values 
+/// that are reduced into a single output value. Backward rules produce a
+/// gradient in the same matrix form which is permuted back again.
+///
+#[derive(Clone)]
+struct Reduction
+{
+    perm: Vec<usize>,
+    permuted: Vec<usize>,
+    outer: usize,
+    inner: usize,
+    out: Shape,
+}
+
+impl Reduction
+{
+    fn new(op: &'static str, shape: &Shape, axes: &[usize], keepdim: bool) -> Result<Self>
+    {
+        let dims = shape.dims();
+        let out = shape.reduce(axes, keepdim).map_err(|e| match e
+        {
+            RuneError::InvalidShape { shape, reason, .. } =>
+                RuneError::InvalidShape { op, shape, reason },
+            e => e,
+        })?;
+        let mut perm: Vec<usize> = (0..dims.len()).filter(|a| !axes.contains(a)).collect();
+        perm.extend_from_slice(axes);
+
+        let permuted: Vec<usize> = perm.iter().map(|&a| dims[a]).collect();
+        let kept = dims.len() - axes.len();
+        Ok(Reduction
+        {
+            outer: permuted[..kept].iter().product(),
+            inner: permuted[kept..].iter().product(),
+            perm,
+            permuted,
+            out,
+        })
+    }
+
+    fn rows<T: DataType>(&self, data: &ArrayD<T>) -> Array2<T>
+    {
+        let permuted = data.view().permuted_axes(self.perm.as_slice());
+        let values = permuted.iter().copied().collect();
+        Array2::from_shape_vec((self.outer, self.inner), values).unwrap()
+    }
+
+    fn output<T>(&self, values: Vec<T>) -> ArrayD<T>
+    {
+        ArrayD::from_shape_vec(IxDyn(self.out.dims()), values).unwrap()
+    }
+
+    ///
+    /// Build the gradient of the input from the gradient of the output. 
+    /// `f(row, col, g)` gives the gradient of input value `col` in `row`, 
+    /// where `g` is the gradient of the output value of that row.
+    ///
+    fn backward<T, F>(&self, grad: &ArrayD<T>, f: F) -> ArrayD<T>
+    where T: DataType, F: Fn(usize, usize, T) -> T
+    {
+        let grad: Vec<T> = grad.iter().copied().collect();
+        let rows = Array2::from_shape_fn((self.outer, self.inner), |(i, j)| f(i, j, grad[i]));
+
+        let mut inverse = vec![0; self.perm.len()];
+        for (i, &axis) in self.perm.iter().enumerate()
+        {
+            inverse[axis] = i;
+        }
+
+        rows.into_shape(IxDyn(&self.permuted))
+            .unwrap()
+            .permuted_axes(inverse)
+            .as_standard_layout()
+            .into_owned()
+    }
+
+    ///
+    /// Position of the first maximum, or minimum, in every row.
+    ///
+    fn arg<T: DataType>(&self, op: &'static str, rows: &Array2<T>, better: Better<T>)
+        -> Result<Vec<usize>>
+    {
+        if self.inner == 0 && self.outer > 0
+        {
+            let reason = "can not select from an empty axis".to_string();
+            return Err(RuneError::invalid(op, &self.permuted, reason));
+        }
+        Ok(rows.outer_iter()
+            .map(|row|
+            {
+                let mut best = 0;
+                for (i, &value) in row.iter().enumerate()
+                {
+                    if better(value, row[best])
+                    {
+                        best = i;
+                    }
+                }
+                best
+            })
+            .collect())
+    }
+}
+
+///
+/// Reductions over one or several axes. The axes can be given in any order
+/// and an empty slice reduces nothing. With `keepdim` the reduced axes are 
+/// kept with size 1, so that the output broadcasts against the input. The
+/// `*_all` variants reduce the whole tensor into a 0-D scalar.
+///
+/// # Example
+///
+/// let t = Tensor::<f32>::ones(&[32, 3, 64, 64]);
+/// let a = t.mean(&[2, 3], true);
+/// let b = t.sum_all();
+///
+/// >>> a.shape() = Shape { dims: [32, 3, 1, 1] }
+/// >>> b.shape() = Shape { dims: [] }
+///
+impl<T: DataType> Tensor<T>
+{
+    pub fn try_sum(&self, axes: &[usize], keepdim: bool) -> Result<Tensor<T>>
+    {
+        let reduction = Reduction::new("sum", self.shape(), axes, keepdim)?;
+        let rows = reduction.rows(&self.data());
+        let values = rows.outer_iter().map(|row| row.sum()).collect();
+        let data = reduction.output(values);
+        Ok(Tensor::from_op(data, vec![self.clone()], ||
+        {
+            Box::new(move |grad| vec![reduction.backward(grad, |_, _, g| g)])
+        }))
+    }
+
+    pub fn sum(&self, axes: &[usize], keepdim: bool) -> Tensor<T>
+    {
+        unwrap(self.try_sum(axes, keepdim))
+    }
+
+    pub fn try_mean(&self, axes: &[usize], keepdim: bool) -> Result<Tensor<T>>
+    {
+        let reduction = Reduction::new("mean", self.shape(), axes, keepdim)?;
+        let n = T::from(reduction.inner).unwrap();
+        let rows = reduction.rows(&self.data());
+        let values = rows.outer_iter().map(|row| row.sum() / n).collect();
+        let data = reduction.output(values);
+        Ok(Tensor::from_op(data, vec![self.clone()], ||
+        {
+            Box::new(move |grad| vec![reduction.backward(grad, |_, _, g| g / n)])
+        }))
+    }
+
+    pub fn mean(&self, axes: &[usize], keepdim: bool) -> Tensor<T>
+    {
+        unwrap(self.try_mean(axes, keepdim))
+    }
+
+    ///
+    /// The gradient is routed to the first maximum of every reduced slice.
+    ///
+    pub fn try_max(&self, axes: &[usize], keepdim: bool) -> Result<Tensor<T>>
+    {
+        self.select("max", axes, keepdim, |value, best| value > best || value.is_nan())
+    }
+
+    pub fn max(&self, axes: &[usize], keepdim: bool) -> Tensor<T>
+    {
+        unwrap(self.try_max(axes, keepdim))
+    }
+
+    ///
+    /// The gradient is routed to the first minimum of every reduced slice.
+    ///
+    pub fn try_min(&self, axes: &[usize], keepdim: bool) -> Result<Tensor<T>>
+    {
+        self.select("min", axes, keepdim, |value, best| value < best || value.is_nan())
+    }
+
+    pub fn min(&self, axes: &[usize], keepdim: bool) -> Tensor<T>
+    {
+        unwrap(self.try_min(axes, keepdim))
+    }
+
+    ///
+    /// d(prod x)/dx_i is the product of all other values in the slice. This
+    /// is computed from prefix and suffix products instead of prod / x_i so
+    /// that it is also correct when the slice contains zeros.
+    ///
+    pub fn try_prod(&self, axes: &[usize], keepdim: bool) -> Result<Tensor<T>>
+    {
+        let reduction = Reduction::new("prod", self.shape(), axes, keepdim)?;
+        let rows = reduction.rows(&self.data());
+        let values = rows.outer_iter().map(|row| row.product()).collect();
+        let data = reduction.output(values);
+        Ok(Tensor::from_op(data, vec![self.clone()], ||
+        {
+            Box::new(move |grad|
+            {
+                let mut others = Array2::<T>::ones(rows.raw_dim());
+                for (row, mut other) in rows.outer_iter().zip(others.outer_iter_mut())
+                {
+                    let mut prefix = T::one();
+                    for (o, &x) in other.iter_mut().zip(row.iter())
+                    {
+                        *o = prefix;
+                        prefix *= x;
+                    }
+                    let mut suffix = T::one();
+                    for (o, &x) in other.iter_mut().zip(row.iter()).rev()
+                    {
+                        *o *= suffix;
+                        suffix *= x;
+                    }
+                }
+                vec![reduction.backward(grad, |i, j, g| g * others[[i, j]])]
+            })
+        }))
+    }
+
+    pub fn prod(&self, axes: &[usize], keepdim: bool) -> Tensor<T>
+    {
+        unwrap(self.try_prod(axes, keepdim))
+    }
+
+    pub fn sum_all(&self) -> Tensor<T>
+    {
+        self.sum(&self.all_axes(), false)
+    }
+
+    pub fn mean_all(&self) -> Tensor<T>
+    {
+        self.mean(&self.all_axes(), false)
+    }
+
+    pub fn max_all(&self) -> Tensor<T>
+    {
+        self.max(&self.all_axes(), false)
+    }
+
+    pub fn min_all(&self) -> Tensor<T>
+    {
+        self.min(&self.all_axes(), false)
+    }
+
+    pub fn prod_all(&self) -> Tensor<T>
+    {
+        self.prod(&self.all_axes(), false)
+    }
+
+    ///
+    /// Indices of the first maximum along `axis`. Indices are not 
+    /// differentiable, so they are returned as a plain array.
+    ///
+    pub fn try_argmax(&self, axis: usize, keepdim: bool) -> Result<ArrayD<usize>>
+    {
+        self.arg("argmax", axis, keepdim, |value, best| value > best || value.is_nan())
+    }
+
+    pub fn argmax(&self, axis: usize, keepdim: bool) -> ArrayD<usize>
+    {
+        unwrap(self.try_argmax(axis, keepdim))
+    }
+
+    ///
+    /// Indices of the first minimum along `axis`.
+    ///
+    pub fn try_argmin(&self, axis: usize, keepdim: bool) -> Result<ArrayD<usize>>
+    {
+        self.arg("argmin", axis, keepdim, |value, best| value < best || value.is_nan())
+    }
+
+    pub fn argmin(&self, axis: usize, keepdim: bool) -> ArrayD<usize>
+    {
+        unwrap(self.try_argmin(axis, keepdim))
+    }
+
+    fn all_axes(&self) -> Vec<usize>
+    {
+        (0..self.shape().dims().len()).collect()
+    }
+
+    fn arg(&self, op: &'static str, axis: usize, keepdim: bool, better: Better<T>)
+        -> Result<ArrayD<usize>>
+    {
+        let reduction = Reduction::new(op, self.shape(), &[axis], keepdim)?;
+        let rows = reduction.rows(&self.data());
+        Ok(reduction.output(reduction.arg(op, &rows, better)?))
+    }
+
+
+    fn select(&self, op: &'static str, axes: &[usize], keepdim: bool, better: Better<T>)
+        -> Result<Tensor<T>>
+    {
+        let reduction = Reduction::new(op, self.shape(), axes, keepdim)?;
+        let rows = reduction.rows(&self.data());
+        let indices = reduction.arg(op, &rows, better)?;
+        let values = indices.iter().enumerate().map(|(i, &j)| rows[[i, j]]).collect();
+        let data = reduction.output(values);
+        Ok(Tensor::from_op(data, vec![self.clone()], ||
+        {
+            Box::new(move |grad|
+            {
+                let route = |i: usize, j: usize, g: T| match j == indices[i]
+                {
+                    true => g,
+                    false => T::zero(),
+                };
+                vec![reduction.backward(grad, route)]
+            })
+        }))
+    }
+}
+
+///
+/// FUNCTIONAL UNIT TESTING
+///
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn tensor(dims: &[usize], values: Vec<f64>) -> Tensor<f64>
+    {
+        let mut t = Tensor::new(ArrayD::from_shape_vec(IxDyn(dims), values).unwrap());
+        t.set_requires_grad(true);
+        t
+    }
+
+    #[test]
+    fn shapes()
+    {
+        let t = Tensor::<f32>::ones(&[32, 3, 8, 8]);
+
+        assert_eq!(*t.sum(&[1], false).shape().dims(), vec![32, 8, 8]);
+        assert_eq!(*t.mean(&[2, 3], true).shape().dims(), vec![32, 3, 1, 1]);
+        assert_eq!(*t.max(&[3, 0], false).shape().dims(), vec![3, 8]);
+        assert_eq!(*t.prod(&[], false).shape().dims(), vec![32, 3, 8, 8]);
+        assert_eq!(*t.min_all().shape().dims(), Vec::<usize>::new());
+        assert_eq!(t.argmax(2, true).shape(), &[32, 3, 1, 8]);
+    }
+
+    #[test]
+    fn values()
+    {
+        // [[1, 5, 2],
+        //  [4, 0, 6]]
+        let t = tensor(&[2, 3], vec![1.0, 5.0, 2.0, 4.0, 0.0, 6.0]);
+
+        assert_eq!(t.sum(&[0], false).data().as_slice().unwrap(), &[5.0, 5.0, 8.0]);
+        assert_eq!(t.mean(&[1], false).data().as_slice().unwrap(), &[8.0 / 3.0, 10.0 / 3.0]);
+        assert_eq!(t.max(&[1], true).data().as_slice().unwrap(), &[5.0, 6.0]);
+        assert_eq!(t.min(&[0], false).data().as_slice().unwrap(), &[1.0, 0.0, 2.0]);
+        assert_eq!(t.prod(&[0], false).data().as_slice().unwrap(), &[4.0, 0.0, 12.0]);
+        assert_eq!(t.sum_all().data()[[]], 18.0);
+        assert_eq!(t.argmax(1, false).as_slice().unwrap(), &[1, 2]);
+        assert_eq!(t.argmin(0, false).as_slice().unwrap(), &[0, 1, 0]);
+    }
+
+    #[test]
+    fn gradients()
+    {
+        let t = tensor(&[2, 3], vec![1.0, 5.0, 2.0, 4.0, 0.0, 6.0]);
+
+        t.sum(&[0], false).sum_all().backward();
+        assert_eq!(t.grad().as_slice().unwrap(), &[1.0; 6]);
+
+        t.zero_grad();
+        t.mean(&[0, 1], false).backward();
+        assert_eq!(t.grad().as_slice().unwrap(), &[1.0 / 6.0; 6]);
+
+        t.zero_grad();
+        t.max(&[1], false).sum_all().backward();
+        assert_eq!(t.grad().as_slice().unwrap(), &[0.0, 1.0, 0.0, 0.0, 0.0, 1.0]);
+
+        t.zero_grad();
+        t.min(&[0], true).sum_all().backward();
+        assert_eq!(t.grad().as_slice().unwrap(), &[1.0, 0.0, 1.0, 0.0, 1.0, 0.0]);
+
+        t.zero_grad();
+        t.prod(&[0], false).sum_all().backward();
+        assert_eq!(t.grad().as_slice().unwrap(), &[4.0, 0.0, 6.0, 1.0, 5.0, 2.0]);
+    }
+
+    #[test]
+    fn gradients_several_axes()
+    {
+        let t = tensor(&[2, 2, 2], vec![1.0, 8.0, 3.0, 4.0, 5.0, 6.0, 7.0, 2.0]);
+
+        t.max(&[2, 0], false).sum_all().backward();
+        assert_eq!(t.grad().as_slice().unwrap(), &[0.0, 1.0, 0.0, 0.0, 0.0, 0.0, 1.0, 0.0]);
+
+        t.zero_grad();
+        t.prod_all().backward();
+        let p = 8.0 * 3.0 * 4.0 * 5.0 * 6.0 * 7.0 * 2.0;
+        let expected: Vec<f64> = t.data().iter().map(|x| p / x).collect();
+        assert_eq!(t.grad().as_slice().unwrap(), expected.as_slice());
+    }
+
+    #[test]
+    fn fallible()
+    {
+        let t = Tensor::<f32>::ones(&[3, 0]);
+
+        assert_eq!(
+            t.try_sum(&[2], false).err(),
+            Some(RuneError::invalid("sum", &[3, 0], "axis 2 is out of bounds".to_string())),
+        );
+        assert!(t.try_mean(&[1, 1], false).is_err());
+        assert!(t.try_argmin(1, false).is_err());
+        assert!(t.try_sum(&[1], false).is_ok());
+    }
+
+    #[test]
+    #[should_panic]
+    fn empty_axis()
+    {
+        let _t = Tensor::<f32>::ones(&[3, 0]).max(&[1], false);
+    }
+}
@@ -0,0 +1,168 @@
+//
+// MIT License
+// 
+// Copyright (c) 2023 Wilhelm Ågren
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// 
+// File created: 2026-10-17
+// Last updated: 2026-10-17
+//
+
+use crate::datatype::DataType;
+use crate::error::unwrap;
+use crate::error::Result;
+use crate::error::RuneError;
+use crate::tensor::Tensor;
+
+use ndarray::ArrayD;
+use ndarray::Axis;
+use ndarray::Zip;
+
+///
+/// Softmax and its logarithm along an axis. Both subtract the max of every
+/// lane before exponentiating, so large inputs can not overflow.
+///
+impl<T: DataType> Tensor<T>
+{
+    ///
+    /// exp(x_i) / sum_j exp(x_j) along `axis`.
+    ///
+    /// # Example
+    ///
+    /// let t = Tensor::<f32>::zeros(&[2, 4]);
+    ///
+    /// >>> t.softmax(1).data() = [[0.25, 0.25, 0.25, 0.25], [0.25, 0.25, 0.25, 0.25]]
+    ///
+    pub fn try_softmax(&self, axis: usize) -> Result<Tensor<T>>
+    {
+        let data = self.lanes("softmax", axis, |x, max, sum| (x - max).exp() / sum)?;
+        let output = data.clone();
+        Ok(Tensor::from_op(data, vec![self.clone()], ||
+        {
+            // dx = s * (g - sum(g * s)) along the axis.
+            Box::new(move |grad|
+            {
+                let dot = (grad * &output).sum_axis(Axis(axis)).insert_axis(Axis(axis));
+                vec![&output * &(grad - &dot)]
+            })
+        }))
+    }
+
+    pub fn softmax(&self, axis: usize) -> Tensor<T>
+    {
+        unwrap(self.try_softmax(axis))
+    }
+
+    ///
+    /// x_i - log(sum_j exp(x_j)) along `axis`, which is more accurate than
+    /// taking the log of `softmax` when probabilities are tiny.
+    ///
+    pub fn try_log_softmax(&self, axis: usize) -> Result<Tensor<T>>
+    {
+        let data = self.lanes("log_softmax", axis, |x, max, sum| x - max - sum.ln())?;
+        let softmax = data.mapv(|x| x.exp());
+        Ok(Tensor::from_op(data, vec![self.clone()], ||
+        {
+            // dx = g - softmax * sum(g) along the axis.
+            Box::new(move |grad|
+            {
+                let sum = grad.sum_axis(Axis(axis)).insert_axis(Axis(axis));
+                vec![grad - &(&softmax * &sum)]
+            })
+        }))
+    }
+
+    pub fn log_softmax(&self, axis: usize) -> Tensor<T>
+    {
+        unwrap(self.try_log_softmax(axis))
+    }
+
+    ///
+    /// Copy the data and map every value x to `f(x, max, sum)`, where `max`
+    /// is the max of its lane along `axis` and `sum` is the sum of 
+    /// exp(x - max) over that lane.
+    ///
+    fn lanes<F>(&self, op: &'static str, axis: usize, f: F) -> Result<ArrayD<T>>
+    where F: Fn(T, T, T) -> T
+    {
+        if axis >= self.shape().ndim()
+        {
+            let reason = format!("axis {} is out of bounds", axis);
+            return Err(RuneError::invalid(op, self.shape().dims(), reason));
+        }
+
+        let mut data = self.data().clone();
+        Zip::from(data.lanes_mut(Axis(axis))).for_each(|mut lane|
+        {
+            let max = lane.fold(T::neg_infinity(), |m, &x| m.max(x));
+            let sum = lane.fold(T::zero(), |s, &x| s + (x - max).exp());
+            lane.mapv_inplace(|x| f(x, max, sum));
+        });
+        Ok(data)
+    }
+}
+
+///
+/// FUNCTIONAL UNIT TESTING
+///
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::autograd::gradcheck;
+
+    use ndarray::IxDyn;
+
+    fn tensor(dims: &[usize], values: Vec<f64>) -> Tensor<f64>
+    {
+        let mut t = Tensor::new(ArrayD::from_shape_vec(IxDyn(dims), values).unwrap());
+        t.set_requires_grad(true);
+        t
+    }
+
+    #[test]
+    fn values()
+    {
+        let t = tensor(&[2, 3], vec![1.0, 2.0, 3.0, 1000.0, 1000.0, 1000.0]);
+        let s = t.softmax(1);
+        let l = t.log_softmax(1);
+        let z = 1.0 + 1.0f64.exp() + 2.0f64.exp();
+
+        assert!((s.data()[[0, 2]] - 2.0f64.exp() / z).abs() < 1e-12);
+        assert!((l.data()[[0, 0]] + z.ln()).abs() < 1e-12);
+        assert_eq!(s.data()[[1, 1]], 1.0 / 3.0);
+        assert_eq!(l.data()[[1, 1]], -(3.0f64.ln()));
+        assert!(t.softmax(0).data().iter().all(|x| x.is_finite()));
+        assert!(t.try_softmax(2).is_err());
+    }
+
+    #[test]
+    fn gradients()
+    {
+        let values = vec![0.5, -1.0, 2.0, 0.0, 1.5, -0.5, 0.3, 0.1, -2.0, 1.0, 0.7, 0.2];
+        let x = [tensor(&[2, 3, 2], values)];
+        let w = ArrayD::from_shape_fn(IxDyn(&[2, 3, 2]), |d| (d[0] + 2 * d[1] + 3 * d[2]) as f64);
+        let w = Tensor::new(w);
+
+        assert!(gradcheck(|t| t[0].softmax(1).mul(&w), &x, 1e-6, 1e-6));
+        assert!(gradcheck(|t| t[0].log_softmax(1).mul(&w), &x, 1e-6, 1e-6));
+        assert!(gradcheck(|t| t[0].log_softmax(2).mul(&w), &x, 1e-6, 1e-6));
+    }
+}
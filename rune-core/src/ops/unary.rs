@@ -0,0 +1,229 @@
+//
+// MIT License
+// 
+// Copyright (c) 2023 Wilhelm Ågren
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// 
+// File created: 2026-10-17
+// Last updated: 2026-10-17
+//
+
+use crate::datatype::DataType;
+use crate::tensor::Tensor;
+
+///
+/// Elementwise unary ops. Every op is defined by its function f(x) and its
+/// derivative f'(x), the backward rule is then g * f'(x). The derivative 
+/// is recomputed from the input instead of keeping the output around.
+///
+impl<T: DataType> Tensor<T>
+{
+    pub(crate) fn unary<F, D>(&self, f: F, df: D) -> Tensor<T>
+    where F: Fn(T) -> T, D: Fn(T) -> T + 'static
+    {
+        let data = self.data().mapv(f);
+        Tensor::from_op(data, vec![self.clone()], ||
+        {
+            let input = self.clone();
+            Box::new(move |grad|
+            {
+                let mut d_input = input.data().mapv(&df);
+                d_input *= grad;
+                vec![d_input]
+            })
+        })
+    }
+
+    pub fn neg(&self) -> Tensor<T>
+    {
+        self.unary(|x| -x, |_| -T::one())
+    }
+
+    pub fn exp(&self) -> Tensor<T>
+    {
+        self.unary(|x| x.exp(), |x| x.exp())
+    }
+
+    ///
+    /// Natural logarithm.
+    ///
+    pub fn log(&self) -> Tensor<T>
+    {
+        self.unary(|x| x.ln(), |x| x.recip())
+    }
+
+    pub fn sqrt(&self) -> Tensor<T>
+    {
+        let half = T::from(0.5).unwrap();
+        self.unary(|x| x.sqrt(), move |x| half / x.sqrt())
+    }
+
+    ///
+    /// Raise every value to the power of `exponent`.
+    ///
+    pub fn pow(&self, exponent: T) -> Tensor<T>
+    {
+        self.unary(move |x| x.powf(exponent), move |x| exponent * x.powf(exponent - T::one()))
+    }
+
+    ///
+    /// The subgradient at 0 is taken to be 0.
+    ///
+    pub fn abs(&self) -> Tensor<T>
+    {
+        let sign = |x: T| match x == T::zero()
+        {
+            true => T::zero(),
+            false => x.signum(),
+        };
+        self.unary(|x| x.abs(), sign)
+    }
+
+    pub fn sin(&self) -> Tensor<T>
+    {
+        self.unary(|x| x.sin(), |x| x.cos())
+    }
+
+    pub fn cos(&self) -> Tensor<T>
+    {
+        self.unary(|x| x.cos(), |x| -x.sin())
+    }
+
+    pub fn tanh(&self) -> Tensor<T>
+    {
+        self.unary(|x| x.tanh(), |x| T::one() - x.tanh().powi(2))
+    }
+
+    pub fn sigmoid(&self) -> Tensor<T>
+    {
+        self.unary(sigmoid, |x| sigmoid(x) * (T::one() - sigmoid(x)))
+    }
+
+    ///
+    /// log(1 + exp(x)), computed as max(x, 0) + log(1 + exp(-|x|)) so that
+    /// it neither overflows for large x nor loses precision for small x.
+    ///
+    pub fn softplus(&self) -> Tensor<T>
+    {
+        self.unary(|x| x.max(T::zero()) + (-x.abs()).exp().ln_1p(), sigmoid)
+    }
+
+    ///
+    /// The subgradient at 0 is taken to be 0.
+    ///
+    pub fn relu(&self) -> Tensor<T>
+    {
+        let step = |x: T| match x > T::zero()
+        {
+            true => T::one(),
+            false => T::zero(),
+        };
+        self.unary(|x| x.max(T::zero()), step)
+    }
+}
+
+///
+/// Numerically stable logistic function, exp is only ever evaluated for 
+/// negative arguments so it can not overflow.
+///
+pub(crate) fn sigmoid<T: DataType>(x: T) -> T
+{
+    match x >= T::zero()
+    {
+        true => T::one() / (T::one() + (-x).exp()),
+        false => x.exp() / (T::one() + x.exp()),
+    }
+}
+
+///
+/// FUNCTIONAL UNIT TESTING
+///
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::autograd::gradcheck;
+
+    use ndarray::ArrayD;
+    use ndarray::IxDyn;
+
+    fn input(values: Vec<f64>) -> Tensor<f64>
+    {
+        let mut t = Tensor::new(ArrayD::from_shape_vec(IxDyn(&[values.len()]), values).unwrap());
+        t.set_requires_grad(true);
+        t
+    }
+
+    #[test]
+    fn values()
+    {
+        let t = input(vec![-2.0, 0.0, 4.0]);
+
+        assert_eq!(t.neg().data().as_slice().unwrap(), &[2.0, 0.0, -4.0]);
+        assert_eq!(t.abs().data().as_slice().unwrap(), &[2.0, 0.0, 4.0]);
+        assert_eq!(t.relu().data().as_slice().unwrap(), &[0.0, 0.0, 4.0]);
+        assert_eq!(t.pow(2.0).data().as_slice().unwrap(), &[4.0, 0.0, 16.0]);
+        assert_eq!(t.abs().sqrt().data().as_slice().unwrap(), &[2.0f64.sqrt(), 0.0, 2.0]);
+        assert_eq!(t.exp().log().data().as_slice().unwrap(), &[-2.0, 0.0, 4.0]);
+        assert_eq!(t.sigmoid().data()[[1]], 0.5);
+        assert_eq!(t.tanh().data()[[1]], 0.0);
+        assert_eq!(t.cos().data()[[1]], 1.0);
+        assert_eq!(t.sin().data()[[1]], 0.0);
+        assert_eq!(t.softplus().data()[[1]], 2.0f64.ln());
+    }
+
+    #[test]
+    fn sigmoid_is_stable()
+    {
+        let t = Tensor::new(ArrayD::from_shape_vec(IxDyn(&[2]), vec![-1000.0f32, 1000.0]).unwrap());
+        assert_eq!(t.sigmoid().data().as_slice().unwrap(), &[0.0, 1.0]);
+        assert_eq!(t.softplus().data().as_slice().unwrap(), &[0.0, 1000.0]);
+    }
+
+    #[test]
+    fn gradients()
+    {
+        // Stay away from the kinks of abs and relu at 0.
+        let x = input(vec![-1.5, -0.3, 0.2, 0.7, 2.0]);
+        let p = input(vec![0.1, 0.5, 1.0, 2.0, 3.0]);
+
+        type Op = fn(&Tensor<f64>) -> Tensor<f64>;
+        let ops: [(Op, &Tensor<f64>); 13] = [
+            (Tensor::neg, &x),
+            (Tensor::exp, &x),
+            (Tensor::log, &p),
+            (Tensor::sqrt, &p),
+            (|t| t.pow(3.0), &x),
+            (|t| t.pow(-1.5), &p),
+            (Tensor::abs, &x),
+            (Tensor::sin, &x),
+            (Tensor::cos, &x),
+            (Tensor::tanh, &x),
+            (Tensor::sigmoid, &x),
+            (Tensor::relu, &x),
+            (Tensor::softplus, &x),
+        ];
+        for (op, input) in ops
+        {
+            assert!(gradcheck(|t| op(&t[0]), std::slice::from_ref(input), 1e-6, 1e-6));
+        }
+        assert!(gradcheck(|t| t[0].tanh().mul(&t[1].log()), &[x, p], 1e-6, 1e-6));
+    }
+}
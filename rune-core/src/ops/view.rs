@@ -0,0 +1,389 @@
+//
+// MIT License
+// 
+// Copyright (c) 2023 Wilhelm Ågren
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// 
+// File created: 2026-10-17
+// Last updated: 2026-10-17
+//
+
+use crate::datatype::DataType;
+use crate::error::unwrap;
+use crate::error::Result;
+use crate::error::RuneError;
+use crate::shape::Shape;
+use crate::tensor::Tensor;
+use crate::utils::reduce_to_shape;
+
+use ndarray::IxDyn;
+
+///
+/// Ops that only change the shape of a tensor. Tensors always store their 
+/// data in standard layout, so every op returns a tensor in standard layout
+/// as well. The backward rules bring the gradient back to the input shape.
+///
+impl<T: DataType> Tensor<T>
+{
+    ///
+    /// Reshape into `dims`, where at most one dim can be -1 to infer it from
+    /// the number of elements.
+    ///
+    /// # Example
+    ///
+    /// let t = Tensor::<f32>::ones(&[32, 16, 4, 4]);
+    ///
+    /// >>> t.reshape(&[32, -1]).shape() = Shape { dims: [32, 256] }
+    ///
+    pub fn try_reshape(&self, dims: &[isize]) -> Result<Tensor<T>>
+    {
+        match self.shape().reshape(dims)
+        {
+            Some(shape) => Ok(self.reshaped(shape)),
+            None => Err(RuneError::invalid(
+                "reshape", self.shape().dims(), format!("could not reshape into {:?}", dims),
+            )),
+        }
+    }
+
+    pub fn reshape(&self, dims: &[isize]) -> Tensor<T>
+    {
+        unwrap(self.try_reshape(dims))
+    }
+
+    ///
+    /// Same as `reshape`. There is no storage to share between tensors, so
+    /// a view never aliases the data of its input.
+    ///
+    pub fn try_view(&self, dims: &[isize]) -> Result<Tensor<T>>
+    {
+        self.try_reshape(dims)
+    }
+
+    pub fn view(&self, dims: &[isize]) -> Tensor<T>
+    {
+        unwrap(self.try_view(dims))
+    }
+
+    ///
+    /// Swap two axes.
+    ///
+    pub fn try_transpose(&self, axis_a: usize, axis_b: usize) -> Result<Tensor<T>>
+    {
+        let mut axes: Vec<usize> = (0..self.shape().ndim()).collect();
+        if axis_a >= axes.len() || axis_b >= axes.len()
+        {
+            let reason = format!("could not transpose axes {} and {}", axis_a, axis_b);
+            return Err(RuneError::invalid("transpose", self.shape().dims(), reason));
+        }
+        axes.swap(axis_a, axis_b);
+        self.try_permute(&axes)
+    }
+
+    pub fn transpose(&self, axis_a: usize, axis_b: usize) -> Tensor<T>
+    {
+        unwrap(self.try_transpose(axis_a, axis_b))
+    }
+
+    ///
+    /// Reorder the axes, axis i of the output is axis axes[i] of the input.
+    ///
+    pub fn try_permute(&self, axes: &[usize]) -> Result<Tensor<T>>
+    {
+        if self.shape().permute(axes).is_none()
+        {
+            let reason = format!("could not permute by {:?}", axes);
+            return Err(RuneError::invalid("permute", self.shape().dims(), reason));
+        }
+
+        let data = self.data()
+            .view()
+            .permuted_axes(axes)
+            .as_standard_layout()
+            .into_owned();
+        Ok(Tensor::from_op(data, vec![self.clone()], ||
+        {
+            let mut inverse = vec![0; axes.len()];
+            for (i, &axis) in axes.iter().enumerate()
+            {
+                inverse[axis] = i;
+            }
+            Box::new(move |grad|
+            {
+                let d_input = grad.view()
+                    .permuted_axes(inverse.as_slice())
+                    .as_standard_layout()
+                    .into_owned();
+                vec![d_input]
+            })
+        }))
+    }
+
+    pub fn permute(&self, axes: &[usize]) -> Tensor<T>
+    {
+        unwrap(self.try_permute(axes))
+    }
+
+    ///
+    /// Remove `axis`, which must have size 1.
+    ///
+    pub fn try_squeeze(&self, axis: usize) -> Result<Tensor<T>>
+    {
+        let dims = self.shape().dims();
+        if axis >= dims.len() || dims[axis] != 1
+        {
+            let reason = format!("axis {} does not have size 1", axis);
+            return Err(RuneError::invalid("squeeze", dims, reason));
+        }
+
+        let mut squeezed = dims.clone();
+        squeezed.remove(axis);
+        Ok(self.reshaped(Shape::new(&squeezed)))
+    }
+
+    pub fn squeeze(&self, axis: usize) -> Tensor<T>
+    {
+        unwrap(self.try_squeeze(axis))
+    }
+
+    ///
+    /// Remove all axes of size 1.
+    ///
+    pub fn squeeze_all(&self) -> Tensor<T>
+    {
+        let dims: Vec<usize> = self.shape().dims().iter().copied().filter(|&d| d != 1).collect();
+        self.reshaped(Shape::new(&dims))
+    }
+
+    ///
+    /// Insert an axis of size 1 at position `axis`, which can be at most the
+    /// number of dims.
+    ///
+    pub fn try_unsqueeze(&self, axis: usize) -> Result<Tensor<T>>
+    {
+        let dims = self.shape().dims();
+        if axis > dims.len()
+        {
+            let reason = format!("axis {} is out of bounds", axis);
+            return Err(RuneError::invalid("unsqueeze", dims, reason));
+        }
+
+        let mut unsqueezed = dims.clone();
+        unsqueezed.insert(axis, 1);
+        Ok(self.reshaped(Shape::new(&unsqueezed)))
+    }
+
+    pub fn unsqueeze(&self, axis: usize) -> Tensor<T>
+    {
+        unwrap(self.try_unsqueeze(axis))
+    }
+
+    ///
+    /// Merge the axes from `start` to `end`, both inclusive, into one.
+    ///
+    /// # Example
+    ///
+    /// let t = Tensor::<f32>::ones(&[32, 16, 4, 4]);
+    ///
+    /// >>> t.flatten(1, 3).shape() = Shape { dims: [32, 256] }
+    ///
+    pub fn try_flatten(&self, start: usize, end: usize) -> Result<Tensor<T>>
+    {
+        let dims = self.shape().dims();
+        if dims.is_empty()
+        {
+            return Ok(self.reshaped(Shape::new(&[1])));
+        }
+
+        if start > end || end >= dims.len()
+        {
+            let reason = format!("could not flatten axes {} to {}", start, end);
+            return Err(RuneError::invalid("flatten", dims, reason));
+        }
+
+        let mut flattened = dims[..start].to_vec();
+        flattened.push(dims[start..=end].iter().product());
+        flattened.extend_from_slice(&dims[end + 1..]);
+        Ok(self.reshaped(Shape::new(&flattened)))
+    }
+
+    pub fn flatten(&self, start: usize, end: usize) -> Tensor<T>
+    {
+        unwrap(self.try_flatten(start, end))
+    }
+
+    ///
+    /// Broadcast to `dims`, following the same rules as the elementwise ops.
+    /// The gradient is summed back down to the input shape.
+    ///
+    pub fn try_expand(&self, dims: &[usize]) -> Result<Tensor<T>>
+    {
+        let target = Shape::new(dims);
+        if self.shape().broadcast(&target).as_ref() != Some(&target)
+        {
+            return Err(RuneError::shapes("expand", &[self.shape().dims(), dims]));
+        }
+
+        let data = self.data().broadcast(IxDyn(dims)).unwrap().to_owned();
+        Ok(Tensor::from_op(data, vec![self.clone()], ||
+        {
+            let shape = self.shape().clone();
+            Box::new(move |grad| vec![reduce_to_shape(grad, &shape)])
+        }))
+    }
+
+    pub fn expand(&self, dims: &[usize]) -> Tensor<T>
+    {
+        unwrap(self.try_expand(dims))
+    }
+
+    fn reshaped(&self, shape: Shape) -> Tensor<T>
+    {
+        let data = self.data()
+            .as_standard_layout()
+            .into_owned()
+            .into_shape(IxDyn(shape.dims()))
+            .unwrap();
+        Tensor::from_op(data, vec![self.clone()], ||
+        {
+            let dims = self.shape().dims().clone();
+            Box::new(move |grad|
+            {
+                let d_input = grad.as_standard_layout()
+                    .into_owned()
+                    .into_shape(IxDyn(&dims))
+                    .unwrap();
+                vec![d_input]
+            })
+        })
+    }
+}
+
+///
+/// FUNCTIONAL UNIT TESTING
+///
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::autograd::gradcheck;
+
+    use ndarray::ArrayD;
+
+    fn arange(dims: &[usize]) -> Tensor<f64>
+    {
+        let len = dims.iter().product::<usize>();
+        let data = (0..len).map(|i| i as f64).collect();
+        let mut t = Tensor::new(ArrayD::from_shape_vec(IxDyn(dims), data).unwrap());
+        t.set_requires_grad(true);
+        t
+    }
+
+    #[test]
+    fn shapes()
+    {
+        let t = Tensor::<f32>::ones(&[32, 16, 4, 1]);
+
+        assert_eq!(*t.reshape(&[32, -1]).shape().dims(), vec![32, 64]);
+        assert_eq!(*t.view(&[-1]).shape().dims(), vec![2048]);
+        assert_eq!(*t.transpose(0, 2).shape().dims(), vec![4, 16, 32, 1]);
+        assert_eq!(*t.permute(&[3, 0, 2, 1]).shape().dims(), vec![1, 32, 4, 16]);
+        assert_eq!(*t.squeeze(3).shape().dims(), vec![32, 16, 4]);
+        assert_eq!(*t.squeeze_all().unsqueeze(0).shape().dims(), vec![1, 32, 16, 4]);
+        assert_eq!(*t.unsqueeze(4).shape().dims(), vec![32, 16, 4, 1, 1]);
+        assert_eq!(*t.flatten(1, 3).shape().dims(), vec![32, 64]);
+        assert_eq!(*t.flatten(0, 0).shape().dims(), vec![32, 16, 4, 1]);
+        assert_eq!(*t.expand(&[2, 32, 16, 4, 8]).shape().dims(), vec![2, 32, 16, 4, 8]);
+    }
+
+    #[test]
+    fn values()
+    {
+        let t = arange(&[2, 3]);
+
+        assert_eq!(t.transpose(0, 1).data().as_slice().unwrap(), &[0.0, 3.0, 1.0, 4.0, 2.0, 5.0]);
+        assert_eq!(t.reshape(&[3, 2]).data().as_slice().unwrap(), &[0.0, 1.0, 2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(
+            t.reshape(&[2, 3, 1]).expand(&[2, 3, 2]).data().as_slice().unwrap(),
+            &[0.0, 0.0, 1.0, 1.0, 2.0, 2.0, 3.0, 3.0, 4.0, 4.0, 5.0, 5.0],
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_reshape()
+    {
+        let _t = Tensor::<f32>::ones(&[32, 16]).reshape(&[7, -1]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_squeeze()
+    {
+        let _t = Tensor::<f32>::ones(&[32, 16]).squeeze(1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_expand()
+    {
+        let _t = Tensor::<f32>::ones(&[3, 2]).expand(&[3, 4]);
+    }
+
+    #[test]
+    fn gradients()
+    {
+        // Multiply by a non-uniform weight so that a misplaced gradient shows,
+        // everything is linear so a large step is exact.
+        let x = [arange(&[2, 3, 4])];
+        let w = |t: Tensor<f64>|
+        {
+            let weight = arange(t.shape().dims()).data().mapv(|v| v + 1.0);
+            t.mul(&Tensor::new(weight))
+        };
+
+        assert!(gradcheck(|t| w(t[0].reshape(&[4, -1])), &x, 1e-3, 1e-6));
+        assert!(gradcheck(|t| w(t[0].transpose(0, 2)), &x, 1e-3, 1e-6));
+        assert!(gradcheck(|t| w(t[0].permute(&[1, 2, 0])), &x, 1e-3, 1e-6));
+        assert!(gradcheck(|t| w(t[0].unsqueeze(1).squeeze(1)), &x, 1e-3, 1e-6));
+        assert!(gradcheck(|t| w(t[0].flatten(0, 1)), &x, 1e-3, 1e-6));
+        assert!(gradcheck(|t| w(t[0].expand(&[5, 2, 3, 4])), &x, 1e-3, 1e-6));
+    }
+
+    #[test]
+    fn fallible()
+    {
+        let t = Tensor::<f32>::ones(&[32, 16]);
+
+        assert!(t.try_view(&[7, -1]).is_err());
+        assert!(t.try_transpose(0, 2).is_err());
+        assert!(t.try_permute(&[0, 0]).is_err());
+        assert!(t.try_unsqueeze(3).is_err());
+        assert!(t.try_flatten(1, 0).is_err());
+        assert_eq!(
+            t.try_squeeze(1).err(),
+            Some(RuneError::invalid("squeeze", &[32, 16], "axis 1 does not have size 1".to_string())),
+        );
+        assert_eq!(
+            t.try_expand(&[32, 4]).err(),
+            Some(RuneError::shapes("expand", &[&[32, 16], &[32, 4]])),
+        );
+    }
+}
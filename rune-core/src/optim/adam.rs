@@ -0,0 +1,385 @@
+//
+// MIT License
+// 
+// Copyright (c) 2023 Wilhelm Ågren
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// 
+// File created: 2026-10-17
+// Last updated: 2026-10-17
+//
+
+use crate::datatype::DataType;
+use crate::optim::Optimizer;
+use crate::optim::ParamGroup;
+use crate::optim::State;
+use crate::tensor::Tensor;
+
+use ndarray::ArrayD;
+use ndarray::IxDyn;
+use ndarray::Zip;
+
+///
+/// Adam, with bias corrected estimates of the first and second moments of
+/// the gradient. For every parameter p with gradient g, on step t:
+///
+///   g = g + weight_decay * p
+///   m = beta1 * m + (1 - beta1) * g
+///   v = beta2 * v + (1 - beta2) * g^2
+///   p = p - lr * (m / (1 - beta1^t)) / (sqrt(v / (1 - beta2^t)) + eps)
+///
+/// With `amsgrad` the running max of v is used in the denominator instead
+/// of v, so that the effective step size never increases. See `AdamW` for
+/// the variant with decoupled weight decay.
+///
+pub struct Adam<T: DataType>
+{
+    groups: Vec<ParamGroup<T>>,
+    betas: (T, T),
+    eps: T,
+    amsgrad: bool,
+    decoupled: bool,
+    moments: Vec<Vec<Option<Moments<T>>>>,
+}
+
+///
+/// The state of a single parameter. Steps are counted per parameter, so
+/// that a parameter without a gradient on some step is corrected for the
+/// number of updates it actually got.
+///
+struct Moments<T: DataType>
+{
+    step: usize,
+    exp_avg: ArrayD<T>,
+    exp_avg_sq: ArrayD<T>,
+    max_exp_avg_sq: Option<ArrayD<T>>,
+}
+
+impl<T: DataType> Adam<T>
+{
+    pub fn new(params: Vec<Tensor<T>>, lr: T) -> Self
+    {
+        Adam::with_groups(vec![ParamGroup::new(params, lr)])
+    }
+
+    pub fn with_groups(groups: Vec<ParamGroup<T>>) -> Self
+    {
+        let moments = groups.iter().map(|g| g.params.iter().map(|_| None).collect()).collect();
+        Adam
+        {
+            groups,
+            betas: (T::from(0.9).unwrap(), T::from(0.999).unwrap()),
+            eps: T::from(1e-8).unwrap(),
+            amsgrad: false,
+            decoupled: false,
+            moments,
+        }
+    }
+
+    pub fn betas(mut self, beta1: T, beta2: T) -> Self
+    {
+        self.betas = (beta1, beta2);
+        self
+    }
+
+    pub fn eps(mut self, eps: T) -> Self
+    {
+        self.eps = eps;
+        self
+    }
+
+    pub fn amsgrad(mut self, amsgrad: bool) -> Self
+    {
+        self.amsgrad = amsgrad;
+        self
+    }
+
+    ///
+    /// Set the weight decay of every group.
+    ///
+    pub fn weight_decay(mut self, weight_decay: T) -> Self
+    {
+        for group in self.groups.iter_mut()
+        {
+            group.weight_decay = weight_decay;
+        }
+        self
+    }
+}
+
+///
+/// AdamW decouples the weight decay from the gradient, the parameters are
+/// decayed directly by p = p - lr * weight_decay * p before the Adam 
+/// update instead of adding weight_decay * p to the gradient. The decay is
+/// then not rescaled by the second moment, which regularizes parameters 
+/// with large gradients as much as those with small ones.
+///
+/// The weight decay of `new` defaults to 0.01, while the groups given to
+/// `with_groups` keep their own.
+///
+pub struct AdamW {}
+
+impl AdamW
+{
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new<T: DataType>(params: Vec<Tensor<T>>, lr: T) -> Adam<T>
+    {
+        let mut group = ParamGroup::new(params, lr);
+        group.weight_decay = T::from(0.01).unwrap();
+        AdamW::with_groups(vec![group])
+    }
+
+    pub fn with_groups<T: DataType>(groups: Vec<ParamGroup<T>>) -> Adam<T>
+    {
+        let mut adam = Adam::with_groups(groups);
+        adam.decoupled = true;
+        adam
+    }
+}
+
+impl<T: DataType> Optimizer<T> for Adam<T>
+{
+    fn step(&mut self)
+    {
+        let (beta1, beta2) = self.betas;
+        for (group, moments) in self.groups.iter().zip(self.moments.iter_mut())
+        {
+            for (param, moments) in group.params.iter().zip(moments.iter_mut())
+            {
+                if !param.requires_grad()
+                {
+                    continue;
+                }
+
+                let mut grad = param.grad().clone();
+                let mut data = param.data_mut();
+                if group.weight_decay != T::zero()
+                {
+                    match self.decoupled
+                    {
+                        true => data.mapv_inplace(|p| p * (T::one() - group.lr * group.weight_decay)),
+                        false => grad.scaled_add(group.weight_decay, &data),
+                    }
+                }
+
+                let moments = moments.get_or_insert_with(|| Moments
+                {
+                    step: 0,
+                    exp_avg: ArrayD::zeros(grad.raw_dim()),
+                    exp_avg_sq: ArrayD::zeros(grad.raw_dim()),
+                    max_exp_avg_sq: self.amsgrad.then(|| ArrayD::zeros(grad.raw_dim())),
+                });
+                moments.step += 1;
+                Zip::from(&mut moments.exp_avg)
+                    .and(&mut moments.exp_avg_sq)
+                    .and(&grad)
+                    .for_each(|m, v, &g|
+                    {
+                        *m = beta1 * *m + (T::one() - beta1) * g;
+                        *v = beta2 * *v + (T::one() - beta2) * g * g;
+                    });
+
+                let second = match &mut moments.max_exp_avg_sq
+                {
+                    Some(max) =>
+                    {
+                        max.zip_mut_with(&moments.exp_avg_sq, |m, &v| *m = m.max(v));
+                        &*max
+                    },
+                    None => &moments.exp_avg_sq,
+                };
+
+                let step = moments.step as i32;
+                let correction1 = T::one() - beta1.powi(step);
+                let correction2 = (T::one() - beta2.powi(step)).sqrt();
+                let step_size = group.lr / correction1;
+                let eps = self.eps;
+                Zip::from(&mut *data)
+                    .and(&moments.exp_avg)
+                    .and(second)
+                    .for_each(|p, &m, &v| *p -= step_size * m / (v.sqrt() / correction2 + eps));
+            }
+        }
+    }
+
+    fn param_groups(&self) -> &[ParamGroup<T>]
+    {
+        &self.groups
+    }
+
+    fn param_groups_mut(&mut self) -> &mut [ParamGroup<T>]
+    {
+        &mut self.groups
+    }
+
+    fn state(&self) -> State<T>
+    {
+        let mut state = State::new();
+        for (i, moments) in self.moments.iter().enumerate()
+        {
+            for (j, moments) in moments.iter().enumerate()
+            {
+                let moments = match moments
+                {
+                    Some(moments) => moments,
+                    None => continue,
+                };
+                let step = T::from(moments.step).unwrap();
+                state.insert(format!("{}.{}.step", i, j), ArrayD::from_elem(IxDyn(&[]), step));
+                state.insert(format!("{}.{}.exp_avg", i, j), moments.exp_avg.clone());
+                state.insert(format!("{}.{}.exp_avg_sq", i, j), moments.exp_avg_sq.clone());
+                if let Some(max) = &moments.max_exp_avg_sq
+                {
+                    state.insert(format!("{}.{}.max_exp_avg_sq", i, j), max.clone());
+                }
+            }
+        }
+        state
+    }
+}
+
+///
+/// FUNCTIONAL UNIT TESTING
+///
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::nn::parameter::Parameter;
+
+    fn parameter<T: DataType>(values: Vec<T>) -> Tensor<T>
+    {
+        Parameter::new(ArrayD::from_shape_vec(IxDyn(&[values.len()]), values).unwrap())
+    }
+
+    ///
+    /// Run `steps` steps on f(p) = sum(scale * p^2), whose gradient is 
+    /// 2 * scale * p.
+    ///
+    fn run<T: DataType>(optimizer: &mut Adam<T>, p: &Tensor<T>, scale: T, steps: usize)
+    {
+        let scale = Tensor::new(ArrayD::from_elem(IxDyn(&[]), scale));
+        for _ in 0..steps
+        {
+            optimizer.zero_grad();
+            p.mul(p).mul(&scale).sum_all().backward();
+            optimizer.step();
+        }
+    }
+
+    #[test]
+    fn first_step()
+    {
+        // The bias corrected first step is lr * g / (|g| + eps) = lr * sign(g),
+        // whatever the scale of the gradient.
+        let p = parameter(vec![1.0f64, -2.0, 1e-3]);
+        let mut optimizer = Adam::new(vec![p.clone()], 0.1);
+        run(&mut optimizer, &p, 1000.0, 1);
+
+        let expected = [0.9, -1.9, 1e-3 - 0.1];
+        for (a, b) in p.data().iter().zip(expected.iter())
+        {
+            assert!((a - b).abs() < 1e-6);
+        }
+        assert_eq!(optimizer.state()["0.0.step"][[]], 1.0);
+        assert!(!optimizer.state().contains_key("0.0.max_exp_avg_sq"));
+    }
+
+    #[test]
+    fn two_steps()
+    {
+        // g0 = 2, g1 = 2 * 0.9 = 1.8 with f(p) = p^2, beta1 = 0.5, beta2 = 0.5
+        // m = 0.5 * 1 + 0.5 * 1.8 = 1.4, v = 0.5 * 2 + 0.5 * 3.24 = 2.62
+        let p = parameter(vec![1.0f64]);
+        let mut optimizer = Adam::new(vec![p.clone()], 0.1).betas(0.5, 0.5).eps(0.0);
+        run(&mut optimizer, &p, 1.0, 2);
+
+        let m = 1.4 / 0.75;
+        let v: f64 = 2.62 / 0.75;
+        assert!((p.data()[[0]] - (0.9 - 0.1 * m / v.sqrt())).abs() < 1e-12);
+        assert!((optimizer.state()["0.0.exp_avg"][[0]] - 1.4).abs() < 1e-12);
+        assert!((optimizer.state()["0.0.exp_avg_sq"][[0]] - 2.62).abs() < 1e-12);
+    }
+
+    #[test]
+    fn amsgrad()
+    {
+        // A large first gradient keeps the denominator large afterwards.
+        let p = parameter(vec![10.0f64]);
+        let q = parameter(vec![10.0f64]);
+        let mut plain = Adam::new(vec![p.clone()], 0.1).betas(0.0, 0.5);
+        let mut ams = Adam::new(vec![q.clone()], 0.1).betas(0.0, 0.5).amsgrad(true);
+        run(&mut plain, &p, 1.0, 1);
+        run(&mut ams, &q, 1.0, 1);
+        run(&mut plain, &p, 0.001, 1);
+        run(&mut ams, &q, 0.001, 1);
+
+        let state = ams.state();
+        assert_eq!(state["0.0.max_exp_avg_sq"][[0]], 200.0);
+        assert!(state["0.0.exp_avg_sq"][[0]] < 101.0);
+        assert!(10.0 - 0.1 - q.data()[[0]] < 10.0 - 0.1 - p.data()[[0]]);
+    }
+
+    #[test]
+    fn weight_decay()
+    {
+        // Without a gradient only the decoupled decay moves the parameter,
+        // while L2 decay goes through the normalized Adam update.
+        let p = parameter(vec![2.0f64]);
+        let q = parameter(vec![2.0f64]);
+        let mut adam = Adam::new(vec![p.clone()], 0.1).weight_decay(0.5);
+        let mut adamw = AdamW::new(vec![q.clone()], 0.1).weight_decay(0.5);
+        run(&mut adam, &p, 0.0, 1);
+        run(&mut adamw, &q, 0.0, 1);
+
+        assert!((p.data()[[0]] - 1.9).abs() < 1e-6);
+        assert!((q.data()[[0]] - 2.0 * (1.0 - 0.05)).abs() < 1e-12);
+        assert_eq!(AdamW::new(vec![q], 0.1).param_groups()[0].weight_decay, 0.01);
+    }
+
+    #[test]
+    fn group_weight_decay()
+    {
+        // A group without weight decay, e.g. the biases, is left undecayed.
+        let p = parameter(vec![2.0f64]);
+        let q = parameter(vec![2.0f64]);
+        let mut decayed = ParamGroup::new(vec![p.clone()], 0.1);
+        decayed.weight_decay = 0.5;
+        let mut adamw = AdamW::with_groups(vec![decayed, ParamGroup::new(vec![q.clone()], 0.1)]);
+        run(&mut adamw, &p, 0.0, 1);
+
+        assert_eq!(adamw.param_groups()[1].weight_decay, 0.0);
+        assert!((p.data()[[0]] - 2.0 * (1.0 - 0.05)).abs() < 1e-12);
+        assert_eq!(q.data()[[0]], 2.0);
+    }
+
+    #[test]
+    fn converges()
+    {
+        let p = parameter(vec![3.0f32, -4.0, 0.5]);
+        let q = parameter(vec![3.0f64, -4.0, 0.5]);
+        let mut adam = Adam::new(vec![p.clone()], 0.05f32).amsgrad(true);
+        let mut adamw = AdamW::new(vec![q.clone()], 0.05f64);
+        run(&mut adam, &p, 1.0, 1000);
+        run(&mut adamw, &q, 1.0, 1000);
+
+        assert!(p.data().iter().all(|x| x.abs() < 1e-2));
+        assert!(q.data().iter().all(|x| x.abs() < 1e-2));
+    }
+}
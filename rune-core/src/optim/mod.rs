@@ -0,0 +1,102 @@
+//
+// MIT License
+// 
+// Copyright (c) 2023 Wilhelm Ågren
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// 
+// File created: 2026-10-17
+// Last updated: 2026-10-17
+//
+
+pub mod adam;
+pub mod scheduler;
+pub mod sgd;
+
+use crate::datatype::DataType;
+use crate::tensor::Tensor;
+
+use ndarray::ArrayD;
+
+use std::collections::BTreeMap;
+
+///
+/// A set of parameters that share their hyperparameters. Optimizers keep 
+/// a list of groups, so that e.g. the biases can be trained without weight
+/// decay, or a pretrained backbone with a lower learning rate than a new 
+/// head. Learning rate schedulers update the `lr` of every group.
+///
+#[derive(Clone)]
+pub struct ParamGroup<T: DataType>
+{
+    pub params: Vec<Tensor<T>>,
+    pub lr: T,
+    pub weight_decay: T,
+}
+
+impl<T: DataType> ParamGroup<T>
+{
+    pub fn new(params: Vec<Tensor<T>>, lr: T) -> Self
+    {
+        ParamGroup { params, lr, weight_decay: T::zero() }
+    }
+}
+
+///
+/// The buffers of an optimizer, keyed by `{group}.{param}.{buffer}`, e.g.
+/// `0.3.momentum_buffer` for the momentum of the fourth parameter of the
+/// first group. Counters are stored as 0-D arrays.
+///
+pub type State<T> = BTreeMap<String, ArrayD<T>>;
+
+///
+/// Updates parameters in place from the gradients accumulated by 
+/// `backward`. Parameters that do not require grad are skipped.
+///
+/// # Example
+///
+/// let mut optimizer = Sgd::new(model.parameters(), 0.1).momentum(0.9);
+/// for (x, y) in batches
+/// {
+///     optimizer.zero_grad();
+///     mse_loss(&model.forward(&x), &y, Reduction::Mean).backward();
+///     optimizer.step();
+/// }
+///
+pub trait Optimizer<T: DataType>
+{
+    fn step(&mut self);
+
+    fn param_groups(&self) -> &[ParamGroup<T>];
+
+    fn param_groups_mut(&mut self) -> &mut [ParamGroup<T>];
+
+    fn state(&self) -> State<T>;
+
+    fn zero_grad(&self)
+    {
+        for group in self.param_groups()
+        {
+            for param in group.params.iter()
+            {
+                param.zero_grad();
+            }
+        }
+    }
+}
@@ -0,0 +1,657 @@
+//
+// MIT License
+// 
+// Copyright (c) 2023 Wilhelm Ågren
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// 
+// File created: 2026-10-17
+// Last updated: 2026-10-17
+//
+
+use crate::datatype::DataType;
+use crate::optim::Optimizer;
+
+///
+/// Changes the learning rates of an optimizer over the course of training.
+/// Call `step` once per epoch, or once per batch for schedules that are 
+/// defined in batches such as `OneCycleLr`, after `optimizer.step()`.
+///
+/// Every group of the optimizer has its own rate. Schedulers record the
+/// rates of the groups when they are created and compute every following
+/// rate from them, so the groups keep their ratios throughout training.
+/// Creating a scheduler sets the rates for step 0, which for most 
+/// schedules are the initial rates themselves.
+///
+/// # Example
+///
+/// let mut optimizer = Sgd::new(model.parameters(), 0.1);
+/// let mut scheduler = StepLr::new(&mut optimizer, 30, 0.1);
+/// for epoch in 0..90
+/// {
+///     train(&model, &mut optimizer);
+///     scheduler.step(&mut optimizer);
+/// }
+///
+pub trait LrScheduler<T: DataType>
+{
+    fn step(&mut self, optimizer: &mut dyn Optimizer<T>);
+
+    ///
+    /// The rates set by the last step, one per group.
+    ///
+    fn last_lr(&self) -> Vec<T>;
+}
+
+fn base_lrs<T: DataType>(optimizer: &dyn Optimizer<T>) -> Vec<T>
+{
+    optimizer.param_groups().iter().map(|g| g.lr).collect()
+}
+
+fn set_lrs<T: DataType>(optimizer: &mut dyn Optimizer<T>, lrs: &[T])
+{
+    assert_eq!(
+        optimizer.param_groups().len(), lrs.len(),
+        "Scheduler has {} learning rates but the optimizer has {} groups",
+        lrs.len(), optimizer.param_groups().len(),
+    );
+    for (group, &lr) in optimizer.param_groups_mut().iter_mut().zip(lrs.iter())
+    {
+        group.lr = lr;
+    }
+}
+
+///
+/// Interpolate from `start` at pct = 0 to `end` at pct = 1 along half a 
+/// cosine wave.
+///
+fn cosine<T: DataType>(start: T, end: T, pct: T) -> T
+{
+    let half = T::from(0.5).unwrap();
+    let pi = T::from(std::f64::consts::PI).unwrap();
+    end + (start - end) * half * (T::one() + (pi * pct).cos())
+}
+
+///
+/// Decay every rate by `gamma` once every `step_size` steps.
+///
+pub struct StepLr<T: DataType>
+{
+    base_lrs: Vec<T>,
+    epoch: usize,
+    step_size: usize,
+    gamma: T,
+}
+
+impl<T: DataType> StepLr<T>
+{
+    pub fn new(optimizer: &mut dyn Optimizer<T>, step_size: usize, gamma: T) -> Self
+    {
+        assert!(step_size > 0, "StepLr requires a step size larger than 0");
+        StepLr { base_lrs: base_lrs(optimizer), epoch: 0, step_size, gamma }
+    }
+}
+
+impl<T: DataType> LrScheduler<T> for StepLr<T>
+{
+    fn step(&mut self, optimizer: &mut dyn Optimizer<T>)
+    {
+        self.epoch += 1;
+        set_lrs(optimizer, &self.last_lr());
+    }
+
+    fn last_lr(&self) -> Vec<T>
+    {
+        let decay = self.gamma.powi((self.epoch / self.step_size) as i32);
+        self.base_lrs.iter().map(|&lr| lr * decay).collect()
+    }
+}
+
+///
+/// Decay every rate by `gamma` at every milestone step.
+///
+pub struct MultiStepLr<T: DataType>
+{
+    base_lrs: Vec<T>,
+    epoch: usize,
+    milestones: Vec<usize>,
+    gamma: T,
+}
+
+impl<T: DataType> MultiStepLr<T>
+{
+    pub fn new(optimizer: &mut dyn Optimizer<T>, milestones: &[usize], gamma: T) -> Self
+    {
+        MultiStepLr { base_lrs: base_lrs(optimizer), epoch: 0, milestones: milestones.to_vec(), gamma }
+    }
+}
+
+impl<T: DataType> LrScheduler<T> for MultiStepLr<T>
+{
+    fn step(&mut self, optimizer: &mut dyn Optimizer<T>)
+    {
+        self.epoch += 1;
+        set_lrs(optimizer, &self.last_lr());
+    }
+
+    fn last_lr(&self) -> Vec<T>
+    {
+        let passed = self.milestones.iter().filter(|&&m| m <= self.epoch).count();
+        let decay = self.gamma.powi(passed as i32);
+        self.base_lrs.iter().map(|&lr| lr * decay).collect()
+    }
+}
+
+///
+/// Decay every rate by `gamma` on every step.
+///
+pub struct ExponentialLr<T: DataType>
+{
+    base_lrs: Vec<T>,
+    epoch: usize,
+    gamma: T,
+}
+
+impl<T: DataType> ExponentialLr<T>
+{
+    pub fn new(optimizer: &mut dyn Optimizer<T>, gamma: T) -> Self
+    {
+        ExponentialLr { base_lrs: base_lrs(optimizer), epoch: 0, gamma }
+    }
+}
+
+impl<T: DataType> LrScheduler<T> for ExponentialLr<T>
+{
+    fn step(&mut self, optimizer: &mut dyn Optimizer<T>)
+    {
+        self.epoch += 1;
+        set_lrs(optimizer, &self.last_lr());
+    }
+
+    fn last_lr(&self) -> Vec<T>
+    {
+        let decay = self.gamma.powi(self.epoch as i32);
+        self.base_lrs.iter().map(|&lr| lr * decay).collect()
+    }
+}
+
+///
+/// Anneal every rate from its initial value down to `eta_min` along a 
+/// cosine over a cycle of `t_0` steps, then restart from the initial rate.
+/// Every cycle is `t_mult` times longer than the one before, a `t_mult` of
+/// 1 gives cycles of equal length.
+///
+pub struct CosineAnnealingWarmRestarts<T: DataType>
+{
+    base_lrs: Vec<T>,
+    epoch: usize,
+    t_0: usize,
+    t_mult: usize,
+    eta_min: T,
+}
+
+impl<T: DataType> CosineAnnealingWarmRestarts<T>
+{
+    pub fn new(optimizer: &mut dyn Optimizer<T>, t_0: usize, t_mult: usize, eta_min: T) -> Self
+    {
+        assert!(t_0 > 0 && t_mult > 0, "CosineAnnealingWarmRestarts requires t_0 and t_mult larger than 0");
+        CosineAnnealingWarmRestarts { base_lrs: base_lrs(optimizer), epoch: 0, t_0, t_mult, eta_min }
+    }
+}
+
+impl<T: DataType> LrScheduler<T> for CosineAnnealingWarmRestarts<T>
+{
+    fn step(&mut self, optimizer: &mut dyn Optimizer<T>)
+    {
+        self.epoch += 1;
+        set_lrs(optimizer, &self.last_lr());
+    }
+
+    fn last_lr(&self) -> Vec<T>
+    {
+        let mut t_cur = self.epoch;
+        let mut t_i = self.t_0;
+        while t_cur >= t_i
+        {
+            t_cur -= t_i;
+            t_i *= self.t_mult;
+        }
+        let pct = T::from(t_cur).unwrap() / T::from(t_i).unwrap();
+        self.base_lrs.iter().map(|&lr| cosine(lr, self.eta_min, pct)).collect()
+    }
+}
+
+///
+/// Ramp every rate linearly from `start_factor` times the initial rate up
+/// to the initial rate over `warmup_steps` steps, then hand over to the 
+/// `inner` schedule, which starts from its own step 0 at that point.
+///
+/// # Example
+///
+/// let inner = CosineAnnealingWarmRestarts::new(&mut optimizer, 100, 1, 0.0);
+/// let scheduler = LinearWarmup::new(&mut optimizer, 5, 0.01, inner);
+///
+pub struct LinearWarmup<T: DataType, S: LrScheduler<T>>
+{
+    epoch: usize,
+    warmup_steps: usize,
+    start_factor: T,
+    inner: S,
+}
+
+impl<T: DataType, S: LrScheduler<T>> LinearWarmup<T, S>
+{
+    pub fn new(optimizer: &mut dyn Optimizer<T>, warmup_steps: usize, start_factor: T, inner: S) -> Self
+    {
+        let warmup = LinearWarmup { epoch: 0, warmup_steps, start_factor, inner };
+        set_lrs(optimizer, &warmup.last_lr());
+        warmup
+    }
+
+    pub fn inner(&self) -> &S
+    {
+        &self.inner
+    }
+}
+
+impl<T: DataType, S: LrScheduler<T>> LrScheduler<T> for LinearWarmup<T, S>
+{
+    fn step(&mut self, optimizer: &mut dyn Optimizer<T>)
+    {
+        self.epoch += 1;
+        match self.epoch > self.warmup_steps
+        {
+            true => self.inner.step(optimizer),
+            false => set_lrs(optimizer, &self.last_lr()),
+        }
+    }
+
+    fn last_lr(&self) -> Vec<T>
+    {
+        if self.epoch >= self.warmup_steps
+        {
+            return self.inner.last_lr();
+        }
+        let pct = T::from(self.epoch).unwrap() / T::from(self.warmup_steps).unwrap();
+        let factor = self.start_factor + (T::one() - self.start_factor) * pct;
+        self.inner.last_lr().iter().map(|&lr| lr * factor).collect()
+    }
+}
+
+///
+/// The one cycle policy. Every rate starts at max_lr / div_factor, rises 
+/// along a cosine to max_lr over the first `pct_start` of `total_steps` 
+/// and then anneals down to max_lr / (div_factor * final_div_factor) over
+/// the remaining steps. It is meant to be stepped after every batch.
+///
+/// `max_lrs` holds one rate per group, or a single rate for all groups.
+/// The usual values are a `pct_start` of 0.3, a `div_factor` of 25 and a 
+/// `final_div_factor` of 1e4.
+///
+pub struct OneCycleLr<T: DataType>
+{
+    max_lrs: Vec<T>,
+    epoch: usize,
+    total_steps: usize,
+    pct_start: T,
+    div_factor: T,
+    final_div_factor: T,
+}
+
+impl<T: DataType> OneCycleLr<T>
+{
+    pub fn new(
+        optimizer: &mut dyn Optimizer<T>,
+        max_lrs: &[T],
+        total_steps: usize,
+        pct_start: T,
+        div_factor: T,
+        final_div_factor: T,
+    ) -> Self
+    {
+        let groups = optimizer.param_groups().len();
+        assert!(total_steps > 1, "OneCycleLr requires at least 2 steps");
+        assert!(
+            max_lrs.len() == 1 || max_lrs.len() == groups,
+            "OneCycleLr got {} max rates for {} groups", max_lrs.len(), groups,
+        );
+        let max_lrs = match max_lrs.len()
+        {
+            1 => vec![max_lrs[0]; groups],
+            _ => max_lrs.to_vec(),
+        };
+
+        let scheduler = OneCycleLr
+        {
+            max_lrs,
+            epoch: 0,
+            total_steps,
+            pct_start,
+            div_factor,
+            final_div_factor,
+        };
+        set_lrs(optimizer, &scheduler.last_lr());
+        scheduler
+    }
+}
+
+impl<T: DataType> LrScheduler<T> for OneCycleLr<T>
+{
+    fn step(&mut self, optimizer: &mut dyn Optimizer<T>)
+    {
+        assert!(
+            self.epoch + 1 < self.total_steps,
+            "OneCycleLr stepped more than {} times", self.total_steps - 1,
+        );
+        self.epoch += 1;
+        set_lrs(optimizer, &self.last_lr());
+    }
+
+    fn last_lr(&self) -> Vec<T>
+    {
+        let epoch = T::from(self.epoch).unwrap();
+        let last = T::from(self.total_steps - 1).unwrap();
+        let peak = (self.pct_start * T::from(self.total_steps).unwrap() - T::one()).max(T::zero());
+        self.max_lrs
+            .iter()
+            .map(|&max_lr|
+            {
+                let initial = max_lr / self.div_factor;
+                let min = initial / self.final_div_factor;
+                match epoch <= peak
+                {
+                    true if peak > T::zero() => cosine(initial, max_lr, epoch / peak),
+                    true => max_lr,
+                    false => cosine(max_lr, min, (epoch - peak) / (last - peak)),
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlateauMode
+{
+    Min,
+    Max,
+}
+
+///
+/// Decay every rate by `factor` once a metric, e.g. the validation loss, 
+/// has not improved for more than `patience` steps. A value improves on 
+/// the best one seen so far if it is better by more than a relative 
+/// `threshold`. After a decay no further decay happens for `cooldown` 
+/// steps, and no rate goes below `min_lr`.
+///
+/// This scheduler is driven by the metric, so it does not implement 
+/// `LrScheduler` and is stepped with `step(&mut optimizer, metric)`.
+///
+pub struct ReduceLrOnPlateau<T: DataType>
+{
+    mode: PlateauMode,
+    factor: T,
+    patience: usize,
+    threshold: T,
+    cooldown: usize,
+    min_lr: T,
+    best: Option<T>,
+    bad_steps: usize,
+    cooldown_left: usize,
+    last_lr: Vec<T>,
+}
+
+impl<T: DataType> ReduceLrOnPlateau<T>
+{
+    pub fn new(optimizer: &dyn Optimizer<T>, mode: PlateauMode) -> Self
+    {
+        ReduceLrOnPlateau
+        {
+            mode,
+            factor: T::from(0.1).unwrap(),
+            patience: 10,
+            threshold: T::from(1e-4).unwrap(),
+            cooldown: 0,
+            min_lr: T::zero(),
+            best: None,
+            bad_steps: 0,
+            cooldown_left: 0,
+            last_lr: base_lrs(optimizer),
+        }
+    }
+
+    pub fn factor(mut self, factor: T) -> Self
+    {
+        assert!(factor < T::one(), "ReduceLrOnPlateau requires a factor below 1");
+        self.factor = factor;
+        self
+    }
+
+    pub fn patience(mut self, patience: usize) -> Self
+    {
+        self.patience = patience;
+        self
+    }
+
+    pub fn threshold(mut self, threshold: T) -> Self
+    {
+        self.threshold = threshold;
+        self
+    }
+
+    pub fn cooldown(mut self, cooldown: usize) -> Self
+    {
+        self.cooldown = cooldown;
+        self
+    }
+
+    pub fn min_lr(mut self, min_lr: T) -> Self
+    {
+        self.min_lr = min_lr;
+        self
+    }
+
+    pub fn step(&mut self, optimizer: &mut dyn Optimizer<T>, metric: T)
+    {
+        let improved = match (self.best, self.mode)
+        {
+            (None, _) => true,
+            (Some(best), PlateauMode::Min) => metric < best * (T::one() - self.threshold),
+            (Some(best), PlateauMode::Max) => metric > best * (T::one() + self.threshold),
+        };
+        match improved
+        {
+            true =>
+            {
+                self.best = Some(metric);
+                self.bad_steps = 0;
+            },
+            false => self.bad_steps += 1,
+        }
+
+        if self.cooldown_left > 0
+        {
+            self.cooldown_left -= 1;
+            self.bad_steps = 0;
+        }
+
+        if self.bad_steps > self.patience
+        {
+            for group in optimizer.param_groups_mut()
+            {
+                group.lr = (group.lr * self.factor).max(self.min_lr);
+            }
+            self.cooldown_left = self.cooldown;
+            self.bad_steps = 0;
+        }
+        self.last_lr = base_lrs(optimizer);
+    }
+
+    pub fn last_lr(&self) -> Vec<T>
+    {
+        self.last_lr.clone()
+    }
+}
+
+///
+/// FUNCTIONAL UNIT TESTING
+///
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::nn::parameter::Parameter;
+    use crate::optim::sgd::Sgd;
+    use crate::optim::ParamGroup;
+
+    use ndarray::ArrayD;
+    use ndarray::IxDyn;
+
+    fn optimizer(lrs: &[f64]) -> Sgd<f64>
+    {
+        let groups = lrs.iter()
+            .map(|&lr| ParamGroup::new(vec![Parameter::new(ArrayD::zeros(IxDyn(&[1])))], lr))
+            .collect();
+        Sgd::with_groups(groups)
+    }
+
+    fn lrs(optimizer: &Sgd<f64>) -> Vec<f64>
+    {
+        base_lrs(optimizer)
+    }
+
+    ///
+    /// The first group rate after each of `steps` steps.
+    ///
+    fn trace<S: LrScheduler<f64>>(scheduler: &mut S, optimizer: &mut Sgd<f64>, steps: usize) -> Vec<f64>
+    {
+        (0..steps)
+            .map(|_|
+            {
+                scheduler.step(optimizer);
+                lrs(optimizer)[0]
+            })
+            .collect()
+    }
+
+    fn close(a: &[f64], b: &[f64]) -> bool
+    {
+        a.len() == b.len() && a.iter().zip(b.iter()).all(|(a, b)| (a - b).abs() < 1e-12)
+    }
+
+    #[test]
+    fn step_decays()
+    {
+        let mut opt = optimizer(&[1.0, 0.1]);
+        let mut step = StepLr::new(&mut opt, 2, 0.5);
+        assert!(close(&trace(&mut step, &mut opt, 5), &[1.0, 0.5, 0.5, 0.25, 0.25]));
+        assert!(close(&lrs(&opt), &[0.25, 0.025]));
+
+        let mut opt = optimizer(&[1.0]);
+        let mut multi = MultiStepLr::new(&mut opt, &[1, 4], 0.1);
+        assert!(close(&trace(&mut multi, &mut opt, 4), &[0.1, 0.1, 0.1, 0.01]));
+
+        let mut opt = optimizer(&[2.0]);
+        let mut exponential = ExponentialLr::new(&mut opt, 0.5);
+        assert!(close(&trace(&mut exponential, &mut opt, 3), &[1.0, 0.5, 0.25]));
+        assert!(close(&exponential.last_lr(), &[0.25]));
+    }
+
+    #[test]
+    fn cosine_restarts()
+    {
+        let mut opt = optimizer(&[1.0, 2.0]);
+        let mut cosine = CosineAnnealingWarmRestarts::new(&mut opt, 2, 2, 0.0);
+        let trace = trace(&mut cosine, &mut opt, 7);
+
+        // Cycles of 2 and 4 steps, then a restart at step 6.
+        let c = |pct: f64| 0.5 * (1.0 + (std::f64::consts::PI * pct).cos());
+        assert!(close(&trace, &[c(0.5), 1.0, c(0.25), c(0.5), c(0.75), 1.0, c(0.125)]));
+        assert!(close(&[lrs(&opt)[1]], &[2.0 * trace[6]]));
+    }
+
+    #[test]
+    fn warmup()
+    {
+        let mut opt = optimizer(&[1.0, 0.1]);
+        let inner = StepLr::new(&mut opt, 2, 0.5);
+        let mut warmup = LinearWarmup::new(&mut opt, 4, 0.2, inner);
+
+        assert!(close(&lrs(&opt), &[0.2, 0.02]));
+        assert!(close(&trace(&mut warmup, &mut opt, 7), &[0.4, 0.6, 0.8, 1.0, 1.0, 0.5, 0.5]));
+        assert!(close(&warmup.last_lr(), &[0.5, 0.05]));
+    }
+
+    #[test]
+    fn one_cycle()
+    {
+        let mut opt = optimizer(&[1.0, 1.0]);
+        let mut cycle = OneCycleLr::new(&mut opt, &[1.0, 10.0], 11, 0.3, 10.0, 100.0);
+
+        assert!(close(&lrs(&opt), &[0.1, 1.0]));
+        let trace = trace(&mut cycle, &mut opt, 10);
+
+        // The peak is at step 0.3 * 11 - 1 = 2.3, the last step hits the minimum.
+        assert!(trace[..2].windows(2).all(|w| w[0] < w[1]));
+        assert!(trace[2..].windows(2).all(|w| w[0] > w[1]));
+        assert!(trace[1] > 0.9 && trace[1] < 1.0);
+        assert!(close(&[trace[9]], &[0.001]));
+        assert!(close(&lrs(&opt), &[0.001, 0.01]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn one_cycle_overrun()
+    {
+        let mut opt = optimizer(&[1.0]);
+        let mut cycle = OneCycleLr::new(&mut opt, &[1.0], 3, 0.3, 25.0, 1e4);
+        for _ in 0..3
+        {
+            cycle.step(&mut opt);
+        }
+    }
+
+    #[test]
+    fn plateau()
+    {
+        let mut opt = optimizer(&[1.0, 0.1]);
+        let mut plateau = ReduceLrOnPlateau::new(&opt, PlateauMode::Min)
+            .factor(0.5)
+            .patience(1)
+            .cooldown(1)
+            .min_lr(0.04);
+
+        let mut trace = Vec::new();
+        for metric in [1.0, 0.5, 0.5, 0.5, 0.5, 0.5, 0.5, 0.4, 0.4]
+        {
+            plateau.step(&mut opt, metric);
+            trace.push(lrs(&opt)[0]);
+        }
+
+        // Two bad steps decay, the next one is cooled down, then two more decay.
+        assert!(close(&trace, &[1.0, 1.0, 1.0, 0.5, 0.5, 0.5, 0.25, 0.25, 0.25]));
+        assert!(close(&plateau.last_lr(), &[0.25, 0.04]));
+
+        let mut opt = optimizer(&[1.0]);
+        let mut plateau = ReduceLrOnPlateau::new(&opt, PlateauMode::Max).patience(0);
+        plateau.step(&mut opt, 0.5);
+        plateau.step(&mut opt, 0.9);
+        plateau.step(&mut opt, 0.8);
+        assert!(close(&lrs(&opt), &[0.1]));
+    }
+}
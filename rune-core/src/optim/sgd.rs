@@ -0,0 +1,279 @@
+//
+// MIT License
+// 
+// Copyright (c) 2023 Wilhelm Ågren
+// 
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+// 
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+// 
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+// 
+// File created: 2026-10-17
+// Last updated: 2026-10-17
+//
+
+use crate::datatype::DataType;
+use crate::optim::Optimizer;
+use crate::optim::ParamGroup;
+use crate::optim::State;
+use crate::tensor::Tensor;
+
+use ndarray::ArrayD;
+
+///
+/// Stochastic gradient descent with optional momentum, dampening, Nesterov
+/// momentum and L2 weight decay. For every parameter p with gradient g:
+///
+///   g = g + weight_decay * p
+///   b = momentum * b + (1 - dampening) * g, with b = g on the first step
+///   g = g + momentum * b if nesterov, otherwise g = b
+///   p = p - lr * g
+///
+/// Without momentum the update is plain p = p - lr * g.
+///
+pub struct Sgd<T: DataType>
+{
+    groups: Vec<ParamGroup<T>>,
+    momentum: T,
+    dampening: T,
+    nesterov: bool,
+    buffers: Vec<Vec<Option<ArrayD<T>>>>,
+}
+
+impl<T: DataType> Sgd<T>
+{
+    pub fn new(params: Vec<Tensor<T>>, lr: T) -> Self
+    {
+        Sgd::with_groups(vec![ParamGroup::new(params, lr)])
+    }
+
+    pub fn with_groups(groups: Vec<ParamGroup<T>>) -> Self
+    {
+        let buffers = groups.iter().map(|g| vec![None; g.params.len()]).collect();
+        Sgd
+        {
+            groups,
+            momentum: T::zero(),
+            dampening: T::zero(),
+            nesterov: false,
+            buffers,
+        }
+    }
+
+    pub fn momentum(mut self, momentum: T) -> Self
+    {
+        self.momentum = momentum;
+        self
+    }
+
+    pub fn dampening(mut self, dampening: T) -> Self
+    {
+        self.dampening = dampening;
+        self
+    }
+
+    pub fn nesterov(mut self, nesterov: bool) -> Self
+    {
+        self.nesterov = nesterov;
+        self
+    }
+
+    ///
+    /// Set the weight decay of every group.
+    ///
+    pub fn weight_decay(mut self, weight_decay: T) -> Self
+    {
+        for group in self.groups.iter_mut()
+        {
+            group.weight_decay = weight_decay;
+        }
+        self
+    }
+}
+
+impl<T: DataType> Optimizer<T> for Sgd<T>
+{
+    fn step(&mut self)
+    {
+        let damping = T::one() - self.dampening;
+        for (group, buffers) in self.groups.iter().zip(self.buffers.iter_mut())
+        {
+            for (param, buffer) in group.params.iter().zip(buffers.iter_mut())
+            {
+                if !param.requires_grad()
+                {
+                    continue;
+                }
+
+                let mut grad = param.grad().clone();
+                if group.weight_decay != T::zero()
+                {
+                    grad.scaled_add(group.weight_decay, &param.data());
+                }
+
+                if self.momentum != T::zero()
+                {
+                    let momentum = self.momentum;
+                    let buffer = match buffer
+                    {
+                        Some(buffer) =>
+                        {
+                            buffer.zip_mut_with(&grad, |b, &g| *b = momentum * *b + damping * g);
+                            buffer
+                        },
+                        None => buffer.insert(grad.clone()),
+                    };
+                    match self.nesterov
+                    {
+                        true => grad.scaled_add(momentum, buffer),
+                        false => grad.assign(buffer),
+                    }
+                }
+
+                param.data_mut().scaled_add(-group.lr, &grad);
+            }
+        }
+    }
+
+    fn param_groups(&self) -> &[ParamGroup<T>]
+    {
+        &self.groups
+    }
+
+    fn param_groups_mut(&mut self) -> &mut [ParamGroup<T>]
+    {
+        &mut self.groups
+    }
+
+    fn state(&self) -> State<T>
+    {
+        let mut state = State::new();
+        for (i, buffers) in self.buffers.iter().enumerate()
+        {
+            for (j, buffer) in buffers.iter().enumerate()
+            {
+                if let Some(buffer) = buffer
+                {
+                    state.insert(format!("{}.{}.momentum_buffer", i, j), buffer.clone());
+                }
+            }
+        }
+        state
+    }
+}
+
+///
+/// FUNCTIONAL UNIT TESTING
+///
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use crate::nn::parameter::Parameter;
+
+    use ndarray::IxDyn;
+
+    fn parameter(values: Vec<f64>) -> Tensor<f64>
+    {
+        Parameter::new(ArrayD::from_shape_vec(IxDyn(&[values.len()]), values).unwrap())
+    }
+
+    ///
+    /// Run `steps` steps on f(p) = sum(p^2 / 2), whose gradient is p.
+    ///
+    fn run(optimizer: &mut Sgd<f64>, p: &Tensor<f64>, steps: usize)
+    {
+        for _ in 0..steps
+        {
+            optimizer.zero_grad();
+            p.mul(p).sum_all().div(&Tensor::new(ArrayD::from_elem(IxDyn(&[]), 2.0))).backward();
+            optimizer.step();
+        }
+    }
+
+    #[test]
+    fn plain()
+    {
+        let p = parameter(vec![1.0, -2.0]);
+        let mut optimizer = Sgd::new(vec![p.clone()], 0.5);
+        run(&mut optimizer, &p, 1);
+
+        assert_eq!(p.data().as_slice().unwrap(), &[0.5, -1.0]);
+        assert!(optimizer.state().is_empty());
+    }
+
+    #[test]
+    fn momentum()
+    {
+        // p0 = 1, g0 = 1, b0 = 1, p1 = 0.9
+        //          g1 = 0.9, b1 = 0.9 * 1 + 0.9 = 1.8, p2 = 0.9 - 0.18 = 0.72
+        let p = parameter(vec![1.0]);
+        let mut optimizer = Sgd::new(vec![p.clone()], 0.1).momentum(0.9);
+        run(&mut optimizer, &p, 2);
+
+        assert!((p.data()[[0]] - 0.72).abs() < 1e-12);
+        assert!((optimizer.state()["0.0.momentum_buffer"][[0]] - 1.8).abs() < 1e-12);
+    }
+
+    #[test]
+    fn nesterov_dampening_weight_decay()
+    {
+        // g0 = 1 + 0.5 = 1.5, b0 = 1.5, g = 1.5 + 0.9 * 1.5 = 2.85, p1 = 1 - 0.285
+        let p = parameter(vec![1.0]);
+        let mut optimizer = Sgd::new(vec![p.clone()], 0.1).momentum(0.9).nesterov(true).weight_decay(0.5);
+        run(&mut optimizer, &p, 1);
+        assert!((p.data()[[0]] - 0.715).abs() < 1e-12);
+
+        // b0 = 1 on the first step, then b1 = 0.9 * 1 + (1 - 0.5) * 0.9 = 1.35
+        let q = parameter(vec![1.0]);
+        let mut optimizer = Sgd::new(vec![q.clone()], 0.1).momentum(0.9).dampening(0.5);
+        run(&mut optimizer, &q, 2);
+        assert!((q.data()[[0]] - (0.9 - 0.135)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn groups()
+    {
+        let a = parameter(vec![1.0]);
+        let b = parameter(vec![1.0]);
+        let mut frozen = parameter(vec![1.0]);
+        frozen.set_requires_grad(false);
+        let mut optimizer = Sgd::with_groups(vec![
+            ParamGroup::new(vec![a.clone()], 0.5),
+            ParamGroup::new(vec![b.clone(), frozen.clone()], 0.1),
+        ]);
+        a.add(&b.mul(&b)).add(&frozen).sum_all().backward();
+        optimizer.step();
+
+        assert_eq!(a.data()[[0]], 0.5);
+        assert_eq!(b.data()[[0]], 0.8);
+        assert_eq!(frozen.data()[[0]], 1.0);
+
+        optimizer.param_groups_mut()[0].lr = 0.0;
+        optimizer.zero_grad();
+        assert!(a.grad().iter().chain(b.grad().iter()).all(|&g| g == 0.0));
+    }
+
+    #[test]
+    fn converges()
+    {
+        let p = parameter(vec![3.0, -4.0, 0.5]);
+        let mut optimizer = Sgd::new(vec![p.clone()], 0.05).momentum(0.9).nesterov(true);
+        run(&mut optimizer, &p, 300);
+
+        assert!(p.data().iter().all(|x| x.abs() < 1e-6));
+    }
+}
@@ -22,9 +22,12 @@
 // SOFTWARE.
 // 
 // File created: 2023-03-09
-// Last updated: 2023-03-09
+// Last updated: 2026-10-17
 //
 
+use crate::error::Result;
+use crate::error::RuneError;
+
 #[derive(Clone, Debug, Default)]
 pub struct Shape
 {
@@ -48,10 +51,211 @@ impl Shape
         &self.dims
     }
 
-    fn set_dims(&mut self, dims: &[usize])
+    pub fn set_dims(&mut self, dims: &[usize])
     {
         self.dims = dims.to_vec();
     }
+
+    pub fn ndim(&self) -> usize
+    {
+        self.dims.len()
+    }
+
+    ///
+    /// Total number of elements, 1 for a scalar shape.
+    ///
+    pub fn size(&self) -> usize
+    {
+        self.dims.iter().product()
+    }
+
+    ///
+    /// Resolve the dims of a reshape into this many elements. At most one dim
+    /// can be -1, it is inferred from the size. Returns None if the dims do
+    /// not hold exactly as many elements.
+    ///
+    /// # Example
+    ///
+    /// let a = Shape::new(&[32, 3, 8, 8]);
+    ///
+    /// >>> a.reshape(&[32, -1]) = Some(Shape { dims: [32, 192] })
+    ///
+    pub fn reshape(&self, dims: &[isize]) -> Option<Shape>
+    {
+        let inferred = dims.iter().filter(|&&d| d == -1).count();
+        if inferred > 1 || dims.iter().any(|&d| d < -1)
+        {
+            return None;
+        }
+
+        let known: usize = dims.iter().filter(|&&d| d >= 0).map(|&d| d as usize).product();
+        let dims: Vec<usize> = match inferred
+        {
+            0 => dims.iter().map(|&d| d as usize).collect(),
+            _ if known == 0 || !self.size().is_multiple_of(known) => return None,
+            _ => dims.iter()
+                .map(|&d| match d
+                {
+                    -1 => self.size() / known,
+                    d => d as usize,
+                })
+                .collect(),
+        };
+
+        match dims.iter().product::<usize>() == self.size()
+        {
+            true => Some(Shape { dims }),
+            false => None,
+        }
+    }
+
+    ///
+    /// Reorder the dims, dim i of the result is dim axes[i] of this shape. 
+    /// Returns None if `axes` is not a permutation of all the axes.
+    ///
+    pub fn permute(&self, axes: &[usize]) -> Option<Shape>
+    {
+        let mut seen = vec![false; self.dims.len()];
+        if axes.len() != self.dims.len()
+        {
+            return None;
+        }
+
+        for &axis in axes.iter()
+        {
+            if axis >= seen.len() || seen[axis]
+            {
+                return None;
+            }
+            seen[axis] = true;
+        }
+
+        Some(Shape { dims: axes.iter().map(|&a| self.dims[a]).collect() })
+    }
+
+    ///
+    /// Compute the shape that two shapes broadcast to, following the NumPy
+    /// rules. The dims are aligned from the right and every pair of dims 
+    /// must either be equal or one of them must be 1. Missing leading dims
+    /// are treated as 1. Returns None if the shapes are incompatible.
+    ///
+    /// # Example
+    ///
+    /// let a = Shape::new(&[128, 1]);
+    /// let b = Shape::new(&[1, 500]);
+    ///
+    /// >>> a.broadcast(&b) = Some(Shape { dims: [128, 500] })
+    ///
+    pub fn broadcast(&self, other: &Shape) -> Option<Shape>
+    {
+        let ndim = self.dims.len().max(other.dims.len());
+        let mut dims = vec![0; ndim];
+
+        for (i, dim) in dims.iter_mut().enumerate()
+        {
+            let lhs = self.dim_from_right(ndim - 1 - i);
+            let rhs = other.dim_from_right(ndim - 1 - i);
+            *dim = match (lhs, rhs)
+            {
+                (l, r) if l == r => l,
+                (1, r) => r,
+                (l, 1) => l,
+                _ => return None,
+            };
+        }
+
+        Some(Shape { dims })
+    }
+
+    ///
+    /// Compute the shape after reducing over `axes`. With `keepdim` the 
+    /// reduced dims are kept with size 1, otherwise they are removed. Every
+    /// axis must be in range and appear at most once, or an error is 
+    /// returned.
+    ///
+    /// # Example
+    ///
+    /// let a = Shape::new(&[32, 3, 64, 64]);
+    ///
+    /// >>> a.reduce(&[2, 3], true) = Ok(Shape { dims: [32, 3, 1, 1] })
+    /// >>> a.reduce(&[2, 3], false) = Ok(Shape { dims: [32, 3] })
+    ///
+    pub fn reduce(&self, axes: &[usize], keepdim: bool) -> Result<Shape>
+    {
+        for (i, &axis) in axes.iter().enumerate()
+        {
+            if axis >= self.dims.len()
+            {
+                let reason = format!("axis {} is out of bounds", axis);
+                return Err(RuneError::invalid("reduce", &self.dims, reason));
+            }
+            if axes[..i].contains(&axis)
+            {
+                let reason = format!("axis {} is reduced more than once", axis);
+                return Err(RuneError::invalid("reduce", &self.dims, reason));
+            }
+        }
+
+        let dims = self.dims
+            .iter()
+            .enumerate()
+            .filter_map(|(axis, &dim)| match (axes.contains(&axis), keepdim)
+            {
+                (false, _) => Some(dim),
+                (true, true) => Some(1),
+                (true, false) => None,
+            })
+            .collect();
+        Ok(Shape { dims })
+    }
+
+    ///
+    /// Compute the shape of concatenating shapes along `axis`. All shapes 
+    /// must have the same number of dims and agree on every dim but `axis`.
+    /// Returns None if they do not, or if there are no shapes at all.
+    ///
+    /// # Example
+    ///
+    /// let a = Shape::new(&[32, 3, 8]);
+    /// let b = Shape::new(&[32, 5, 8]);
+    ///
+    /// >>> Shape::concat(&[&a, &b], 1) = Some(Shape { dims: [32, 8, 8] })
+    ///
+    pub fn concat(shapes: &[&Shape], axis: usize) -> Option<Shape>
+    {
+        let first = shapes.first()?;
+        if axis >= first.dims.len()
+        {
+            return None;
+        }
+
+        let mut dims = first.dims.clone();
+        dims[axis] = 0;
+        for shape in shapes.iter()
+        {
+            let agrees = shape.dims.len() == dims.len() && shape.dims
+                .iter()
+                .zip(first.dims.iter())
+                .enumerate()
+                .all(|(a, (d, f))| a == axis || d == f);
+            if !agrees
+            {
+                return None;
+            }
+            dims[axis] += shape.dims[axis];
+        }
+
+        Some(Shape { dims })
+    }
+
+    fn dim_from_right(&self, offset: usize) -> usize
+    {
+        match offset < self.dims.len()
+        {
+            true => self.dims[self.dims.len() - 1 - offset],
+            false => 1,
+        }
+    }
 }
 
 impl PartialEq for Shape
@@ -117,6 +321,78 @@ mod tests
         assert_eq!(a.dims().len(), 4);
     }
 
+    #[test]
+    fn broadcast()
+    {
+        let a = Shape::new(&[128, 1]);
+        let b = Shape::new(&[1, 500]);
+        let c = Shape::new(&[8, 1, 6, 1]);
+        let d = Shape::new(&[7, 1, 5]);
+        let e = Shape::new(&[3]);
+        let f = Shape::none();
+
+        assert_eq!(a.broadcast(&b), Some(Shape::new(&[128, 500])));
+        assert_eq!(b.broadcast(&a), Some(Shape::new(&[128, 500])));
+        assert_eq!(c.broadcast(&d), Some(Shape::new(&[8, 7, 6, 5])));
+        assert_eq!(e.broadcast(&f), Some(Shape::new(&[3])));
+        assert_eq!(a.broadcast(&e), Some(Shape::new(&[128, 3])));
+        assert_eq!(b.broadcast(&e), None);
+    }
+
+    #[test]
+    fn reduce()
+    {
+        let a = Shape::new(&[32, 3, 64, 64]);
+
+        assert_eq!(a.reduce(&[2, 3], true), Ok(Shape::new(&[32, 3, 1, 1])));
+        assert_eq!(a.reduce(&[3, 0], false), Ok(Shape::new(&[3, 64])));
+        assert_eq!(a.reduce(&[0, 1, 2, 3], false), Ok(Shape::none()));
+        assert_eq!(a.reduce(&[], false), Ok(a.clone()));
+        assert!(a.reduce(&[4], false).is_err());
+        assert!(a.reduce(&[1, 1], false).is_err());
+    }
+
+    #[test]
+    fn reshape()
+    {
+        let a = Shape::new(&[32, 3, 8, 8]);
+
+        assert_eq!(a.ndim(), 4);
+        assert_eq!(a.size(), 32 * 3 * 8 * 8);
+        assert_eq!(Shape::none().size(), 1);
+        assert_eq!(a.reshape(&[32, -1]), Some(Shape::new(&[32, 192])));
+        assert_eq!(a.reshape(&[-1, 3, 64]), Some(Shape::new(&[32, 3, 64])));
+        assert_eq!(a.reshape(&[6144]), Some(Shape::new(&[6144])));
+        assert_eq!(a.reshape(&[-1, -1]), None);
+        assert_eq!(a.reshape(&[5, -1]), None);
+        assert_eq!(a.reshape(&[32, 3]), None);
+    }
+
+    #[test]
+    fn permute()
+    {
+        let a = Shape::new(&[32, 3, 8, 4]);
+
+        assert_eq!(a.permute(&[0, 2, 3, 1]), Some(Shape::new(&[32, 8, 4, 3])));
+        assert_eq!(a.permute(&[0, 2, 3]), None);
+        assert_eq!(a.permute(&[0, 2, 2, 1]), None);
+        assert_eq!(a.permute(&[0, 2, 4, 1]), None);
+    }
+
+    #[test]
+    fn concat()
+    {
+        let a = Shape::new(&[32, 3, 8]);
+        let b = Shape::new(&[32, 5, 8]);
+        let c = Shape::new(&[16, 5, 8]);
+
+        assert_eq!(Shape::concat(&[&a, &b, &a], 1), Some(Shape::new(&[32, 11, 8])));
+        assert_eq!(Shape::concat(&[&b, &c], 0), Some(Shape::new(&[48, 5, 8])));
+        assert_eq!(Shape::concat(&[&a, &b], 0), None);
+        assert_eq!(Shape::concat(&[&a], 3), None);
+        assert_eq!(Shape::concat(&[], 0), None);
+    }
+
     #[test]
     fn iter()
     {
@@ -126,7 +402,7 @@ mod tests
 
         // Because we move the dims vec to the caller when calling
         // into_iter() we need to clone if we want to use it after.
-        for (x, y) in a.clone().into_iter().zip(dims.into_iter())
+        for (x, y) in a.clone().into_iter().zip(dims)
         {
             assert_eq!(x, y);
         }
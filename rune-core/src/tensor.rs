@@ -22,34 +22,57 @@
 // SOFTWARE.
 // 
 // File created: 2023-03-09
-// Last updated: 2023-03-12
+// Last updated: 2026-10-17
 //
 
+use crate::autograd::GradFn;
 use crate::datatype::DataType;
+use crate::error::unwrap;
+use crate::error::Result;
+use crate::error::RuneError;
+use crate::random::with_global_generator;
+use crate::random::Generator;
 use crate::shape::Shape;
 use crate::utils::*;
 
 use ndarray::Array0;
-use ndarray::Array2;
 use ndarray::ArrayD;
 use ndarray::Ix0;
-use ndarray::Ix2;
 use ndarray::IxDyn;
-use ndarray::prelude::s;
+use ndarray::Zip;
 
 use ndarray_rand::RandomExt;
-use ndarray_rand::rand_distr::Distribution;
 use ndarray_rand::rand_distr::Normal;
 use ndarray_rand::rand_distr::Uniform;
 
-pub struct Tensor<'a, T: DataType>
+use std::cell::Cell;
+use std::cell::Ref;
+use std::cell::RefMut;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+///
+/// A tensor is a cheap, reference counted handle to a node in the autograd
+/// graph. Cloning a tensor does not copy its data, the clone refers to the
+/// very same node. Every op result owns handles to its parents, so tensors
+/// can be moved, stored in structs and returned from functions freely while
+/// the graph stays alive for as long as anything refers to it.
+///
+#[derive(Clone)]
+pub struct Tensor<T: DataType>
+{
+    node: Rc<Node<T>>,
+}
+
+struct Node<T: DataType>
 {
     shape: Shape,
-    data: ArrayD<T>,
-    parents: Vec<&'a Tensor<'a, T>>,
-    requires_grad: bool,
+    data: RefCell<ArrayD<T>>,
+    parents: Vec<Tensor<T>>,
+    requires_grad: Cell<bool>,
     dtype: T,
-    grad: ArrayD<T>,
+    grad: RefCell<ArrayD<T>>,
+    grad_fn: Option<GradFn<T>>,
 }
 
 ///
@@ -65,250 +88,358 @@ pub struct Tensor<'a, T: DataType>
 ///
 /// >>> [0.0, shape=[], strides=[], layout=CFcf (0xf), dynamic ndim=0]
 ///
-impl<T: DataType> Default for Tensor<'_, T>
+impl<T: DataType> Default for Tensor<T>
 {
     fn default() -> Self
     {
-        Tensor
-        {
-            shape: Shape::none(),
-            data: Array0::<T>::zeros(Ix0()).into_dyn(),
-            parents: Vec::new(),
-            requires_grad: false,
-            dtype: T::default(),
-            grad: Array0::<T>::zeros(Ix0()).into_dyn(),
-        }
+        Tensor::new(Array0::<T>::zeros(Ix0()).into_dyn())
     }
 }
 
-impl<T: DataType> Tensor<'_, T>
+impl<T: DataType> Tensor<T>
 {
     pub fn new(data: ArrayD<T>) -> Self
     {
-        let shape = Shape::new(data.shape());
-        Tensor
-        {
-            shape: shape,
-            data: data,
-            ..Default::default()
-        }
+        Tensor::from_parts(data, Vec::new(), false, None)
     }
 
     pub fn zeros(dims: &[usize]) -> Self
     {
-        let data = ArrayD::<T>::zeros(IxDyn(dims));
-        Tensor
-        {
-            shape: Shape::new(dims),
-            data: data,
-            ..Default::default()
-        }
+        Tensor::new(ArrayD::<T>::zeros(IxDyn(dims)))
     }
 
     pub fn ones(dims: &[usize]) -> Self
     {
-        let data = ArrayD::<T>::ones(IxDyn(dims));
-        Tensor
+        Tensor::new(ArrayD::<T>::ones(IxDyn(dims)))
+    }
+
+    ///
+    /// Sample from U(low, high) using `generator`. Values are drawn in f64 
+    /// and cast to T, so that the random constructors work for every data
+    /// type. The variants without a generator draw from the global one, see
+    /// `random::manual_seed`.
+    ///
+    pub fn try_uniform_with(dims: &[usize], low: f32, high: f32, generator: &mut Generator) -> Result<Self>
+    {
+        if !(low.is_finite() && high.is_finite() && low < high)
         {
-            shape: Shape::new(dims),
-            data: data,
-            ..Default::default()
+            return Err(RuneError::InvalidDistribution(format!(
+                "uniform requires finite bounds with low < high, got [{}, {})", low, high,
+            )));
         }
+        let dist = Uniform::new(low as f64, high as f64);
+        let data = ArrayD::<f64>::random_using(dims, dist, generator);
+        Ok(Tensor::new(data.mapv(|v| T::from(v).unwrap())))
+    }
+
+    pub fn uniform_with(dims: &[usize], low: f32, high: f32, generator: &mut Generator) -> Self
+    {
+        unwrap(Tensor::try_uniform_with(dims, low, high, generator))
+    }
+
+    pub fn try_uniform(dims: &[usize], low: f32, high: f32) -> Result<Self>
+    {
+        with_global_generator(|g| Tensor::try_uniform_with(dims, low, high, g))
     }
 
     pub fn uniform(dims: &[usize], low: f32, high: f32) -> Self
-    where Uniform<f32>: Distribution<T>
     {
-        let dist = Uniform::new(low, high);
-        let data = ArrayD::<T>::random(dims, dist);
-        Tensor
-        {
-            shape: Shape::new(dims),
-            data: data,
-            ..Default::default()
-        }
+        unwrap(Tensor::try_uniform(dims, low, high))
     }
 
-    pub fn normal(dims: &[usize], mu: f32, sigma: f32) -> Self
-    where Normal<f32>: Distribution<T>
+    pub fn try_normal_with(dims: &[usize], mu: f32, sigma: f32, generator: &mut Generator) -> Result<Self>
     {
-        let dist = match Normal::new(mu, sigma)
+        let dist = match Normal::new(mu as f64, sigma as f64)
         {
             Ok(dist) => dist,
-            Err(e) => panic!("Provided variance is not finite, {:?}", e),
+            Err(e) => return Err(RuneError::InvalidDistribution(format!(
+                "normal with mu={} and sigma={}, {}", mu, sigma, e,
+            ))),
         };
-        let data = ArrayD::<T>::random(dims, dist);
-        Tensor
-        {
-            shape: Shape::new(dims),
-            data: data,
-            ..Default::default()
-        }
+        let data = ArrayD::<f64>::random_using(dims, dist, generator);
+        Ok(Tensor::new(data.mapv(|v| T::from(v).unwrap())))
+    }
+
+    pub fn normal_with(dims: &[usize], mu: f32, sigma: f32, generator: &mut Generator) -> Self
+    {
+        unwrap(Tensor::try_normal_with(dims, mu, sigma, generator))
+    }
+
+    pub fn try_normal(dims: &[usize], mu: f32, sigma: f32) -> Result<Self>
+    {
+        with_global_generator(|g| Tensor::try_normal_with(dims, mu, sigma, g))
+    }
+
+    pub fn normal(dims: &[usize], mu: f32, sigma: f32) -> Self
+    {
+        unwrap(Tensor::try_normal(dims, mu, sigma))
     }
 
     pub fn shape(&self) -> &Shape
     {
-        &self.shape
+        &self.node.shape
+    }
+
+    pub fn data(&self) -> Ref<'_, ArrayD<T>>
+    {
+        self.node.data.borrow()
     }
 
-    pub fn data(&self) -> &ArrayD<T>
+    ///
+    /// Mutable access to the data, shared by every handle to this tensor.
+    /// Writes are not recorded in the graph, so this is meant for updating 
+    /// parameters in between steps, e.g. by an optimizer or initializer, 
+    /// and not for computations that should be differentiated.
+    ///
+    pub fn data_mut(&self) -> RefMut<'_, ArrayD<T>>
     {
-        &self.data
+        self.node.data.borrow_mut()
     }
 
     pub fn requires_grad(&self) -> bool
     {
-        self.requires_grad
+        self.node.requires_grad.get()
     }
 
     pub fn set_requires_grad(&mut self, requires_grad: bool)
     {
-        self.requires_grad = requires_grad;
+        self.node.requires_grad.set(requires_grad);
         if requires_grad
         {
-            let dims = self.shape.dims().as_slice();
-            self.grad = ArrayD::<T>::zeros(IxDyn(dims));
+            let dims = self.shape().dims().as_slice();
+            *self.node.grad.borrow_mut() = ArrayD::<T>::zeros(IxDyn(dims));
         }
     }
 
+    pub fn grad(&self) -> Ref<'_, ArrayD<T>>
+    {
+        self.node.grad.borrow()
+    }
+
+    pub fn zero_grad(&self)
+    {
+        self.node.grad.borrow_mut().fill(T::zero());
+    }
+
+    ///
+    /// A leaf tensor was created by the user and not as the result of an op,
+    /// i.e. it is where the gradients end up after calling `backward`.
+    ///
+    pub fn is_leaf(&self) -> bool
+    {
+        self.node.grad_fn.is_none()
+    }
+
+    ///
+    /// Create a new leaf tensor that shares no graph history with this one.
+    /// The data is copied, so the detached tensor never requires grad.
+    ///
+    pub fn detach(&self) -> Self
+    {
+        Tensor::new(self.data().clone())
+    }
+
     pub fn dtype(&self) -> T
     {
-        self.dtype
+        self.node.dtype
     }
-}
 
-///
-/// Lifetime dependent struct functions.
-///
-impl<'a, T: DataType> Tensor<'a, T>
-{
-    fn parents(&self) -> &Vec<&'a Tensor<'a, T>>
+    pub(crate) fn parents(&self) -> &Vec<Tensor<T>>
+    {
+        &self.node.parents
+    }
+
+    pub(crate) fn grad_fn(&self) -> Option<&GradFn<T>>
+    {
+        self.node.grad_fn.as_ref()
+    }
+
+    pub(crate) fn accumulate_grad(&self, grad: &ArrayD<T>)
+    {
+        *self.node.grad.borrow_mut() += grad;
+    }
+
+    ///
+    /// Address of the graph node, used to identify a tensor when several
+    /// handles refer to the same node.
+    ///
+    pub(crate) fn id(&self) -> *const ()
+    {
+        Rc::as_ptr(&self.node) as *const ()
+    }
+
+    ///
+    /// Every tensor stores its data in standard layout, ops are free to rely
+    /// on `as_slice` of the data succeeding.
+    ///
+    fn from_parts(
+        data: ArrayD<T>,
+        parents: Vec<Tensor<T>>,
+        requires_grad: bool,
+        grad_fn: Option<GradFn<T>>,
+    ) -> Self
+    {
+        let data = match data.is_standard_layout()
+        {
+            true => data,
+            false => data.as_standard_layout().into_owned(),
+        };
+        let grad = match requires_grad
+        {
+            true => ArrayD::<T>::zeros(data.raw_dim()),
+            false => Array0::<T>::zeros(Ix0()).into_dyn(),
+        };
+        let node = Node
+        {
+            shape: Shape::new(data.shape()),
+            data: RefCell::new(data),
+            parents,
+            requires_grad: Cell::new(requires_grad),
+            dtype: T::default(),
+            grad: RefCell::new(grad),
+            grad_fn,
+        };
+        Tensor { node: Rc::new(node) }
+    }
+
+    ///
+    /// Create the output tensor of an op. The backward rule is only built
+    /// if any of the parents requires grad, this way we do not hold on to
+    /// any op context when it is not needed. The parents are only kept
+    /// around for the same reason.
+    ///
+    pub(crate) fn from_op<F>(data: ArrayD<T>, parents: Vec<Tensor<T>>, grad_fn: F) -> Self
+    where F: FnOnce() -> GradFn<T>
     {
-        &self.parents
+        match any_requires_grad(parents.iter().collect())
+        {
+            true => Tensor::from_parts(data, parents, true, Some(grad_fn())),
+            false => Tensor::from_parts(data, Vec::new(), false, None),
+        }
     }
 }
 
 ///
 /// Binary ops
 ///
-impl<'a, T: DataType + 'static> Tensor<'a, T>
+impl<T: DataType> Tensor<T>
 {
-    pub fn add(&'a self, other: &'a Tensor<T>) -> Tensor<'a, T>
-    {
-        let data = &self.data + &other.data;
-        let dims = Shape::new(data.shape());
-        let requires_grad = any_requires_grad(vec![self, other]);
-        let parents = vec![self, other];
-        let grad = ArrayD::<T>::zeros(data.shape());
-        Tensor
+    ///
+    /// Apply `f` elementwise over both tensors after broadcasting them to 
+    /// their common shape, see `Shape::broadcast`. Unlike the arithmetic ops
+    /// of ndarray this broadcasts both operands, e.g. [128, 1] + [1, 500].
+    ///
+    fn broadcast_with<F>(&self, op: &'static str, other: &Tensor<T>, f: F) -> Result<ArrayD<T>>
+    where F: Fn(T, T) -> T
+    {
+        let shape = match self.shape().broadcast(other.shape())
         {
-            shape: dims,
-            data: data,
-            parents: parents,
-            requires_grad: requires_grad,
-            dtype: T::default(),
-            grad: grad,
-        }
+            Some(shape) => shape,
+            None => return Err(RuneError::shapes(
+                op, &[self.shape().dims(), other.shape().dims()],
+            )),
+        };
+        let dims = IxDyn(shape.dims());
+        let lhs = self.data();
+        let rhs = other.data();
+        Ok(Zip::from(&lhs.broadcast(dims.clone()).unwrap())
+            .and(&rhs.broadcast(dims).unwrap())
+            .map_collect(|&l, &r| f(l, r)))
     }
 
-    pub fn sub(&'a self, other: &'a Tensor<T>) -> Tensor<'a, T>
+    pub fn try_add(&self, other: &Tensor<T>) -> Result<Tensor<T>>
     {
-        let data = &self.data - &other.data;
-        let dims = Shape::new(data.shape());
-        let requires_grad = any_requires_grad(vec![self, other]);
-        let parents = vec![self, other];
-        let grad = ArrayD::<T>::zeros(data.shape());
-        Tensor
+        let data = self.broadcast_with("add", other, |l, r| l + r)?;
+        Ok(Tensor::from_op(data, vec![self.clone(), other.clone()], ||
         {
-            shape: dims,
-            data: data,
-            parents: parents,
-            requires_grad: requires_grad,
-            dtype: T::default(),
-            grad: grad,
-        }
+            let lhs = self.shape().clone();
+            let rhs = other.shape().clone();
+            Box::new(move |grad|
+            {
+                vec![reduce_to_shape(grad, &lhs), reduce_to_shape(grad, &rhs)]
+            })
+        }))
     }
 
-    pub fn mul(&'a self, other: &'a Tensor<T>) -> Tensor<'a, T>
+    pub fn add(&self, other: &Tensor<T>) -> Tensor<T>
     {
-        let data = &self.data * &other.data;
-        let dims = Shape::new(data.shape());
-        let requires_grad = any_requires_grad(vec![self, other]);
-        let parents = vec![self, other];
-        let grad = ArrayD::<T>::zeros(data.shape());
-        Tensor
-        {
-            shape: dims,
-            data: data,
-            parents: parents,
-            requires_grad: requires_grad,
-            dtype: T::default(),
-            grad: grad,
-        }
+        unwrap(self.try_add(other))
     }
 
-    pub fn div(&'a self, other: &'a Tensor<T>) -> Tensor<'a, T>
+    pub fn try_sub(&self, other: &Tensor<T>) -> Result<Tensor<T>>
     {
-        let data = &self.data / &other.data;
-        let dims = Shape::new(data.shape());
-        let requires_grad = any_requires_grad(vec![self, other]);
-        let parents = vec![self, other];
-        let grad = ArrayD::<T>::zeros(data.shape());
-        Tensor
+        let data = self.broadcast_with("sub", other, |l, r| l - r)?;
+        Ok(Tensor::from_op(data, vec![self.clone(), other.clone()], ||
         {
-            shape: dims,
-            data: data,
-            parents: parents,
-            requires_grad: requires_grad,
-            dtype: T::default(),
-            grad: grad,
-        }
+            let lhs = self.shape().clone();
+            let rhs = other.shape().clone();
+            Box::new(move |grad|
+            {
+                let d_rhs = grad.mapv(|g| -g);
+                vec![reduce_to_shape(grad, &lhs), reduce_to_shape(&d_rhs, &rhs)]
+            })
+        }))
+    }
+
+    pub fn sub(&self, other: &Tensor<T>) -> Tensor<T>
+    {
+        unwrap(self.try_sub(other))
     }
 
     ///
-    /// output (i, k) = (i, j) @ (j, k)
+    /// d(a * b)/da = b
+    /// d(a * b)/db = a
     ///
-    /// a = (i, j)
-    /// b = (j, k)
-    /// t = zeros(i, k)
+    pub fn try_mul(&self, other: &Tensor<T>) -> Result<Tensor<T>>
+    {
+        let data = self.broadcast_with("mul", other, |l, r| l * r)?;
+        Ok(Tensor::from_op(data, vec![self.clone(), other.clone()], ||
+        {
+            let lhs = self.clone();
+            let rhs = other.clone();
+            Box::new(move |grad|
+            {
+                let d_lhs = grad * &*rhs.data();
+                let d_rhs = grad * &*lhs.data();
+                vec![
+                    reduce_to_shape(&d_lhs, lhs.shape()),
+                    reduce_to_shape(&d_rhs, rhs.shape()),
+                ]
+            })
+        }))
+    }
+
+    pub fn mul(&self, other: &Tensor<T>) -> Tensor<T>
+    {
+        unwrap(self.try_mul(other))
+    }
+
     ///
-    /// for i in range 0..i
-    ///     for j in range 0..k
-    ///         for k in range 0..j
-    ///             t[i, j] += a[i, k] * b[k, j]
-    /// return t
+    /// d(a / b)/da = 1 / b
+    /// d(a / b)/db = -a / b^2
     ///
-    pub fn matmul(&'a self, other: &'a Tensor<T>) -> Tensor<'a, T>
+    pub fn try_div(&self, other: &Tensor<T>) -> Result<Tensor<T>>
     {
-        let lhs_dims = self.shape.dims();
-        let rhs_dims = other.shape.dims();
-        let dims = &[lhs_dims[0], rhs_dims[1]];
-        let mut data = ArrayD::<T>::zeros(IxDyn(dims));
-
-        for i in 0..lhs_dims[0]
+        let data = self.broadcast_with("div", other, |l, r| l / r)?;
+        Ok(Tensor::from_op(data, vec![self.clone(), other.clone()], ||
         {
-            for j in 0..rhs_dims[1] 
+            let lhs = self.clone();
+            let rhs = other.clone();
+            Box::new(move |grad|
             {
-                for k in 0..lhs_dims[1]
-                {
-                    data[[i, j]] += self.data()[[i, k]] + other.data()[[k, j]];
-                }
-            }
-        }
+                let d_lhs = grad / &*rhs.data();
+                let d_rhs = -(&d_lhs * &*lhs.data()) / &*rhs.data();
+                vec![
+                    reduce_to_shape(&d_lhs, lhs.shape()),
+                    reduce_to_shape(&d_rhs, rhs.shape()),
+                ]
+            })
+        }))
+    }
 
-        let dims = Shape::new(dims);
-        let requires_grad = any_requires_grad(vec![self, other]);
-        let parents = vec![self, other];
-        let grad = ArrayD::<T>::zeros(data.shape());
-        Tensor
-        {
-            shape: dims,
-            data: data,
-            parents: parents,
-            requires_grad: requires_grad,
-            dtype: T::default(),
-            grad: grad,
-        }
+    pub fn div(&self, other: &Tensor<T>) -> Tensor<T>
+    {
+        unwrap(self.try_div(other))
     }
 }
 
@@ -327,8 +458,8 @@ mod tests
         let _c = Tensor::<f32>::ones(&[128, 3, 256, 256]);
         let _d = Tensor::<f64>::zeros(&[128, 784]);
 
-        let _e = Tensor::uniform(&[128, 3, 256, 256], -1.0, 1.0);
-        let _f = Tensor::normal(&[128, 3, 256, 256], 0.0, 3.1415);
+        let _e = Tensor::<f32>::uniform(&[128, 3, 256, 256], -1.0, 1.0);
+        let _f = Tensor::<f32>::normal(&[128, 3, 256, 256], 0.0, 3.0);
     }
 
     #[test]
@@ -350,10 +481,122 @@ mod tests
         let mut c = Tensor::<f32>::ones(&[128, 3, 256, 256]);
         c.set_requires_grad(true);
 
-        assert_eq!(any_requires_grad(vec![&a, &b]), false);
+        assert!(!any_requires_grad(vec![&a, &b]));
 
         let d = b.add(&c);
-        assert_eq!(any_requires_grad(d.parents().to_vec()), true);
+        assert!(any_requires_grad(d.parents().iter().collect()));
+        assert!(d.requires_grad());
+        assert!(!d.is_leaf());
+    }
+
+    #[test]
+    fn ownership()
+    {
+        fn affine(x: &Tensor<f64>, w: &Tensor<f64>, b: &Tensor<f64>) -> Tensor<f64>
+        {
+            x.mul(w).add(b)
+        }
+
+        let mut w = Tensor::<f64>::ones(&[1]);
+        let mut b = Tensor::<f64>::ones(&[1]);
+        w.set_requires_grad(true);
+        b.set_requires_grad(true);
+
+        let mut x = Tensor::<f64>::ones(&[1]);
+        for _ in 0..3
+        {
+            x = affine(&x, &w, &b);
+        }
+
+        let stored = [x.clone(), w.clone()];
+        drop(x);
+        stored[0].backward();
+
+        // x3 = w^3 + w^2 + w + 1, so dx3/dw = 3w^2 + 2w + 1 = 6
+        assert_eq!(stored[0].data()[[0]], 4.0);
+        assert_eq!(w.grad()[[0]], 6.0);
+        assert_eq!(stored[1].grad()[[0]], 6.0);
+        assert_eq!(b.grad()[[0]], 3.0);
+    }
+
+    #[test]
+    fn detach()
+    {
+        let mut a = Tensor::<f32>::ones(&[2, 2]);
+        a.set_requires_grad(true);
+        let b = a.add(&a).detach();
+
+        assert!(b.is_leaf());
+        assert!(!b.requires_grad());
+        assert_eq!(*b.data(), ArrayD::<f32>::from_elem(IxDyn(&[2, 2]), 2.0));
+    }
+
+    #[test]
+    fn broadcasting()
+    {
+        let a = Tensor::<f32>::ones(&[128, 1]);
+        let b = Tensor::<f32>::ones(&[1, 500]);
+        let c = Tensor::<f32>::ones(&[500]);
+
+        assert_eq!(*a.add(&b).shape().dims(), vec![128, 500]);
+        assert_eq!(*a.sub(&c).shape().dims(), vec![128, 500]);
+        assert_eq!(*c.mul(&a).shape().dims(), vec![128, 500]);
+        assert_eq!(*b.div(&a).data(), ArrayD::<f32>::ones(IxDyn(&[128, 500])));
+    }
+
+    #[test]
+    #[should_panic]
+    fn broadcasting_mismatch()
+    {
+        let a = Tensor::<f32>::ones(&[128, 3]);
+        let b = Tensor::<f32>::ones(&[1, 500]);
+        let _c = a.add(&b);
+    }
+
+    #[test]
+    fn fallible()
+    {
+        let a = Tensor::<f32>::ones(&[128, 3]);
+        let b = Tensor::<f32>::ones(&[1, 500]);
+
+        assert_eq!(
+            a.try_mul(&b).err(),
+            Some(RuneError::shapes("mul", &[&[128, 3], &[1, 500]])),
+        );
+        assert!(a.try_matmul(&b).is_err());
+        assert!(matches!(
+            Tensor::<f32>::try_uniform(&[2], 1.0, -1.0),
+            Err(RuneError::InvalidDistribution(_)),
+        ));
+        assert!(matches!(
+            Tensor::<f32>::try_normal(&[2], 0.0, f32::NAN),
+            Err(RuneError::InvalidDistribution(_)),
+        ));
+        assert!(Tensor::<f32>::try_normal(&[2], 0.0, 1.0).is_ok());
+    }
+
+    #[test]
+    fn matmul()
+    {
+        let a = Tensor::new(
+            ArrayD::from_shape_vec(IxDyn(&[2, 3]), vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap()
+        );
+        let b = Tensor::new(
+            ArrayD::from_shape_vec(IxDyn(&[3, 2]), vec![1.0, 0.0, 0.0, 1.0, 1.0, 1.0]).unwrap()
+        );
+        let c = a.matmul(&b);
+
+        assert_eq!(*c.shape().dims(), vec![2, 2]);
+        assert_eq!(c.data().as_slice().unwrap(), &[4.0, 5.0, 10.0, 11.0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn matmul_mismatch()
+    {
+        let a = Tensor::<f32>::ones(&[2, 3]);
+        let b = Tensor::<f32>::ones(&[2, 3]);
+        let _c = a.matmul(&b);
     }
 
     #[test]
@@ -22,12 +22,21 @@
 // SOFTWARE.
 // 
 // File created: 2023-03-12
-// Last updated: 2023-03-12
+// Last updated: 2026-10-17
 //
 
 use crate::datatype::DataType;
+use crate::error::Result;
+use crate::error::RuneError;
+use crate::shape::Shape;
 use crate::tensor::Tensor;
 
+use ndarray::ArrayBase;
+use ndarray::ArrayD;
+use ndarray::Axis;
+use ndarray::Data;
+use ndarray::Dimension;
+
 use std::any::type_name;
 
 pub fn any_requires_grad<T>(tensors: Vec<&Tensor<T>>) -> bool
@@ -42,10 +51,50 @@ where T: DataType
     type_name::<T>()
 }
 
-pub fn vec_to_array<T, const N: usize>(v: Vec<T>) -> [T; N]
+pub fn vec_to_array<T, const N: usize>(v: Vec<T>) -> Result<[T; N]>
 {
-    v.try_into().unwrap_or_else(
-        |v: Vec<T>| panic!("Expected Vec<T> of length {} but got {}", N, v.len())
+    v.try_into().map_err(
+        |v: Vec<T>| RuneError::shapes("vec_to_array", &[&[N], &[v.len()]])
     )
 }
 
+///
+/// The data of an array as a slice, for the ops that index its raw memory
+/// and so need it to be contiguous in standard layout.
+///
+pub fn contiguous<'a, S, D>(op: &'static str, array: &'a ArrayBase<S, D>) -> Result<&'a [S::Elem]>
+where S: Data, D: Dimension
+{
+    array.as_slice().ok_or(RuneError::NonContiguous { op })
+}
+
+///
+/// Sum a gradient back down to the shape of an operand that was broadcast
+/// in the forward pass. Leading dims that were prepended are summed away
+/// and dims that were stretched from 1 are summed with the axis kept.
+///
+pub fn reduce_to_shape<T>(grad: &ArrayD<T>, shape: &Shape) -> ArrayD<T>
+where T: DataType
+{
+    let dims = shape.dims();
+    if grad.shape() == dims.as_slice()
+    {
+        return grad.clone();
+    }
+
+    let mut reduced = grad.clone();
+    while reduced.ndim() > dims.len()
+    {
+        reduced = reduced.sum_axis(Axis(0));
+    }
+
+    for (axis, &dim) in dims.iter().enumerate()
+    {
+        if dim == 1 && reduced.shape()[axis] != 1
+        {
+            reduced = reduced.sum_axis(Axis(axis)).insert_axis(Axis(axis));
+        }
+    }
+
+    reduced
+}
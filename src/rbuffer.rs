@@ -22,13 +22,13 @@
 // SOFTWARE.
 // 
 // File created: 2023-03-05
-// Last updated: 2023-03-06
+// Last updated: 2026-10-17
 //
 
+use crate::utils::array_from_numpy;
+
 use ndarray::ArrayD;
-use ndarray::Array;
 
-use numpy::PyReadonlyArrayDyn;
 use numpy::PyArrayDyn;
 use numpy::IntoPyArray;
 
@@ -47,19 +47,10 @@ pub struct RBuffer {
 #[pymethods]
 impl RBuffer {
     #[new]
-    fn new(arr: PyReadonlyArrayDyn<f32>) -> Self {
-        let slice_shape = arr.shape();
-        let shape = slice_shape.to_vec();
-        let data_vec = match arr.as_slice() {
-            Ok(raw) => raw.to_vec(),
-            Err(_) => panic!("Could not create immutable view of internal data."),
-        };
-        let data: ArrayD<f32> = Array::from_vec(data_vec).into_dyn();
-        let data = match data.into_shape(slice_shape) {
-            Ok(shaped) => shaped,
-            Err(e) => panic!("Could not reshape ArrayBase to {:?}, {}", slice_shape, e),
-        };
-        RBuffer { shape: shape, data: data }
+    fn new(arr: &PyAny) -> PyResult<Self> {
+        let data = array_from_numpy("RBuffer::new", arr)?;
+        let shape = data.shape().to_vec();
+        Ok(RBuffer { shape: shape, data: data })
     }
 
     fn detach<'py>(&self, py: Python<'py>) -> &'py PyArrayDyn<f32> {
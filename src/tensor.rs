@@ -22,16 +22,15 @@
 // SOFTWARE.
 // 
 // File created: 2023-03-06
-// Last updated: 2023-03-06
+// Last updated: 2026-10-17
 //
 
 use crate::shape::Shape;
+use crate::utils::array_from_numpy;
 
-use numpy::PyReadonlyArrayDyn;
 use numpy::PyArrayDyn;
 use numpy::IntoPyArray;
 
-use ndarray::Array;
 use ndarray::ArrayD;
 use ndarray::IxDyn;
 
@@ -67,53 +66,42 @@ impl Default for Tensor
 impl Tensor
 {
     #[new]
-    pub fn new(np_arr: PyReadonlyArrayDyn<f32>) -> Self
+    pub fn new(np_arr: &PyAny) -> PyResult<Self>
     {
-        let shape_slice = np_arr.shape();
-        let data_vec = match np_arr.as_slice()
-        {
-            Ok(raw) => raw.to_vec(),
-            Err(e) => panic!("Could not create immutable view of internal data, {}.", e),
-        };
-        let data: ArrayD<f32> = Array::from_vec(data_vec).into_dyn();
-        let data = match data.into_shape(shape_slice)
-        {
-            Ok(shaped_arrayd) => shaped_arrayd,
-            Err(e) => panic!("Could not reshape ArrayBase to {:?}, {}.", shape_slice, e),
-        };
-        let shape = Shape::new(shape_slice.to_vec());
-        Tensor
+        let data = array_from_numpy("Tensor::new", np_arr)?;
+        let shape = Shape::new(data.shape().to_vec());
+        Ok(Tensor
         { 
             shape: shape,
             data: data,
             ..Default::default()
-        }
+        })
     }
 
     #[classmethod]
-    pub fn zeros(cls: &PyType, dims: &PyList) -> Self
+    pub fn zeros(cls: &PyType, dims: &PyList) -> PyResult<Self>
     {
-        let dims: Vec<usize> = dims.extract().expect("Could not extract Vec<T> from &PyList");
+        let dims: Vec<usize> = dims.extract()?;
         let data = ArrayD::<f32>::zeros(IxDyn(&dims));
-        Tensor
+        Ok(Tensor
         {
             shape: Shape::new(dims),
             data: data,
             ..Default::default()
-        }
+        })
     }
 
     #[classmethod]
-    pub fn ones(cls: &PyType, dims: &PyList) -> Self
+    pub fn ones(cls: &PyType, dims: &PyList) -> PyResult<Self>
     {
-        let dims: Vec<usize> = dims.extract().expect("Could not extract Vec<T> from &PyList");
+        let dims: Vec<usize> = dims.extract()?;
         let data = ArrayD::<f32>::ones(IxDyn(&dims));
-        Tensor
+        Ok(Tensor
         {
             shape: Shape::new(dims),
             data: data,
             ..Default::default()
-        }
+        })
     }
 
     fn detach<'py>(&self, py: Python<'py>) -> &'py PyArrayDyn<f32>
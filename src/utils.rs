@@ -22,14 +22,56 @@
 // SOFTWARE.
 // 
 // File created: 2023-03-05
-// Last updated: 2023-03-05
+// Last updated: 2026-10-17
 //
 
+use ndarray::ArrayD;
+
+use numpy::PyArrayDyn;
+
+use pyo3::exceptions::PyTypeError;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use rune_core::error::RuneError;
+
+///
+/// Raise a `RuneError` as the matching Python exception.
+///
+pub fn to_py_err(e: RuneError) -> PyErr {
+    match e {
+        RuneError::DTypeMismatch { .. } => PyTypeError::new_err(e.to_string()),
+        _ => PyValueError::new_err(e.to_string()),
+    }
+}
+
+///
+/// Copy a NumPy array into an owned array, which requires it to be float32
+/// and contiguous in standard layout.
+///
+pub fn array_from_numpy(op: &'static str, arr: &PyAny) -> PyResult<ArrayD<f32>> {
+    let arr: &PyArrayDyn<f32> = match arr.extract() {
+        Ok(arr) => arr,
+        Err(_) => {
+            let found = arr.getattr("dtype")?.str()?.to_str()?.to_owned();
+            return Err(to_py_err(RuneError::DTypeMismatch { expected: "float32", found }));
+        },
+    };
+    let arr = arr.readonly();
+    let data = match arr.as_slice() {
+        Ok(raw) => raw.to_vec(),
+        Err(_) => return Err(to_py_err(RuneError::NonContiguous { op })),
+    };
+    ArrayD::from_shape_vec(arr.shape(), data).map_err(
+        |e| PyValueError::new_err(format!("Could not reshape ArrayBase to {:?}, {}", arr.shape(), e))
+    )
+}
+
 #[allow(dead_code)]
-fn vec_to_array<T, const N: usize>(v: Vec<T>) -> [T; N] {
+fn vec_to_array<T, const N: usize>(v: Vec<T>) -> Result<[T; N], String> {
     v.try_into()
-        .unwrap_or_else(
-            |v: Vec<T>| panic!("Expected Vec of length {} but got {}", N, v.len())
+        .map_err(
+            |v: Vec<T>| format!("Expected Vec of length {} but got {}", N, v.len())
         )
 }
 